@@ -0,0 +1,200 @@
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+};
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+use crate::{
+    collections::{HashMap, RandomState},
+    trie_node::TrieNode,
+    Match,
+    TrieTree,
+};
+
+/// 根节点的下标
+const ROOT: usize = 0;
+
+/// 自动机的状态, 对应树中的一个节点
+#[derive(Clone)]
+pub(crate) struct AcState<T, S> {
+    pub childs: HashMap<T, usize, S>,
+    /// 失配时转移到的状态, 其路径为该状态路径的最长真后缀
+    pub fail: usize,
+    /// 沿着失配链最近的终状态
+    pub output: Option<usize>,
+    /// 路径长度
+    pub depth: usize,
+    pub stop: bool,
+}
+
+/// An Aho-Corasick automaton compiled from a [`TrieTree`],
+/// see [`TrieTree::to_aho_corasick`]
+/// ===
+/// Finds all occurrences of all values of the tree in one linear pass
+/// over any `Iterator`, the empty value is never matched.
+///
+/// The automaton owns a copy of the tree: one state per node,
+/// with its own child map of state indices, so it does not borrow the tree.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::{TrieTree, Match};
+/// let tree = TrieTree::from_iter(["he", "she", "his", "hers"].map(str::chars));
+/// let ac = tree.to_aho_corasick();
+/// let found: Vec<Match> = ac.find_iter("ushers".chars()).collect();
+/// assert_eq!(found, [
+///     Match { start: 1, end: 4 },
+///     Match { start: 2, end: 4 },
+///     Match { start: 2, end: 6 },
+/// ]);
+/// assert!(ac.is_match([&'h', &'i', &'s']));
+/// ```
+#[derive(Clone)]
+pub struct AhoCorasick<T, S = RandomState> {
+    /// 所有状态, 第零个为根状态
+    pub(crate) states: Vec<AcState<T, S>>,
+    count: usize,
+}
+
+impl<T, S> TrieTree<T, S>
+where T: Hash + Eq + Clone,
+      S: BuildHasher + Clone,
+{
+    /// Compile the tree into an Aho-Corasick automaton,
+    /// computing the failure and output links of every node<br/>
+    /// Every node and every element is copied into the automaton,
+    /// so it takes about as much memory as the tree itself,
+    /// plus the failure link, the output link and the depth of each node.
+    /// The tree can be dropped or changed afterwards,
+    /// the automaton does not see the changes
+    pub fn to_aho_corasick(&self) -> AhoCorasick<T, S> {
+        let hasher = self.root.childs().hasher().clone();
+        let new_state = |depth, stop| AcState {
+            childs: HashMap::with_capacity_and_hasher(0, hasher.clone()),
+            fail: ROOT,
+            output: None,
+            depth,
+            stop,
+        };
+        let mut states = vec![new_state(0, false)];
+        // 广度优先, 计算一个状态的失配链接时更浅的状态都已完成
+        let mut queue: VecDeque<(&TrieNode<T, S>, usize)> = VecDeque::from([(&self.root, ROOT)]);
+        while let Some((node, index)) = queue.pop_front() {
+            for (data, child) in node.childs() {
+                let child_index = states.len();
+                let mut state = new_state(states[index].depth + 1, child.is_stop());
+                if index != ROOT {
+                    let mut fail = states[index].fail;
+                    state.fail = loop {
+                        if let Some(&next) = states[fail].childs.get(data) {
+                            break next;
+                        }
+                        if fail == ROOT {
+                            break ROOT;
+                        }
+                        fail = states[fail].fail;
+                    };
+                }
+                let fail = &states[state.fail];
+                state.output = if fail.stop { Some(state.fail) } else { fail.output };
+                states.push(state);
+                states[index].childs.insert(data.clone(), child_index);
+                queue.push_back((child, child_index));
+            }
+        }
+        AhoCorasick { states, count: self.count() - self.root.is_stop() as usize }
+    }
+}
+
+impl<T, S> AhoCorasick<T, S> {
+    /// return the number of matchable values, the empty value is excluded
+    pub fn pattern_count(&self) -> usize {
+        self.count
+    }
+
+    /// return the number of states, including the root
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+}
+
+impl<T, S> AhoCorasick<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
+{
+    /// 由状态读入一个元素后转移到的状态
+    pub(crate) fn next_state(&self, mut state: usize, data: &T) -> usize {
+        loop {
+            if let Some(&next) = self.states[state].childs.get(data) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.states[state].fail;
+        }
+    }
+
+    /// 状态处结束的第一个匹配的终状态, 其余的沿着输出链接得到
+    pub(crate) fn first_output(&self, state: usize) -> Option<usize> {
+        let current = &self.states[state];
+        if current.stop { Some(state) } else { current.output }
+    }
+
+    /// Find all occurrences, including the overlapping ones,
+    /// by the element indices of the input<br/>
+    /// The matches are ordered by end, then the longer first
+    pub fn find_iter<Q, I>(&self, iter: I) -> AcIter<'_, T, S, I::IntoIter>
+    where Q: Borrow<T>,
+          I: IntoIterator<Item = Q>,
+    {
+        AcIter {
+            automaton: self,
+            iter: iter.into_iter(),
+            state: ROOT,
+            pos: 0,
+            output: None,
+        }
+    }
+
+    /// Whether any value occurs in the input
+    pub fn is_match<Q>(&self, iter: impl IntoIterator<Item = Q>) -> bool
+    where Q: Borrow<T>
+    {
+        self.find_iter(iter).next().is_some()
+    }
+}
+
+/// Iterator of the matches of [`AhoCorasick::find_iter`]
+pub struct AcIter<'a, T, S, I> {
+    automaton: &'a AhoCorasick<T, S>,
+    iter: I,
+    state: usize,
+    /// 已读入的元素数量
+    pos: usize,
+    /// 当前位置下一个要产生的匹配的终状态
+    output: Option<usize>,
+}
+
+impl<T, S, I, Q> Iterator for AcIter<'_, T, S, I>
+where T: Hash + Eq,
+      S: BuildHasher,
+      I: Iterator<Item = Q>,
+      Q: Borrow<T>,
+{
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(output) = self.output {
+                let state = &self.automaton.states[output];
+                self.output = state.output;
+                return Some(Match { start: self.pos - state.depth, end: self.pos });
+            }
+            let data = self.iter.next()?;
+            self.state = self.automaton.next_state(self.state, data.borrow());
+            self.pos += 1;
+            self.output = self.automaton.first_output(self.state);
+        }
+    }
+}
@@ -0,0 +1,481 @@
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+    ops::{Index, IndexMut},
+};
+use alloc::{vec, vec::Vec};
+
+use crate::collections::{HashMap, HashMapIter, RandomState};
+
+/// 根节点的下标
+pub(crate) const ROOT: usize = 0;
+
+/// 竞技场中的节点, 子节点使用下标引用
+#[derive(Clone)]
+pub(crate) struct ArenaNode<T, V, S> {
+    /// 在该节点结束的值, 为`Some`时是一个停止节点
+    pub value: Option<V>,
+    /// 子节点们的下标
+    pub childs: HashMap<T, usize, S>,
+}
+
+impl<T, V, S> ArenaNode<T, V, S> {
+    fn with_hasher(hasher: S) -> Self {
+        Self {
+            value: None,
+            childs: HashMap::with_capacity_and_hasher(0, hasher),
+        }
+    }
+
+    /// 返回是否是一个停止节点
+    pub fn is_stop(&self) -> bool {
+        self.value.is_some()
+    }
+
+    /// 返回是否是一个可删除的节点
+    /// 判定为非终节点且没有子节点
+    pub fn can_remove(&self) -> bool {
+        self.value.is_none() && self.childs.is_empty()
+    }
+}
+
+/// 将节点存储在`Vec`中的树, 每个停止节点带有一个值
+///
+/// 被删除的节点放入空闲列表, 之后分配节点时优先复用(连同其哈希表的容量)
+#[derive(Clone)]
+pub(crate) struct Arena<T, V, S> {
+    /// 所有节点, 第零个为根节点
+    nodes: Vec<ArenaNode<T, V, S>>,
+    /// 已被删除可以复用的节点下标
+    free: Vec<usize>,
+}
+
+impl<T, V, S> Index<usize> for Arena<T, V, S> {
+    type Output = ArenaNode<T, V, S>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.nodes[index]
+    }
+}
+
+impl<T, V, S> IndexMut<usize> for Arena<T, V, S> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.nodes[index]
+    }
+}
+
+impl<T, V, S> Arena<T, V, S> {
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let mut nodes = Vec::with_capacity(capacity.max(1));
+        nodes.push(ArenaNode::with_hasher(hasher));
+        Self {
+            nodes,
+            free: Vec::new(),
+        }
+    }
+
+    /// 使用中的节点数量, 包括根节点
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    /// 不重新分配时可以容纳的节点数量, 包括空闲节点
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// 迭代节点及其子孙节点的值
+    pub fn values(&self, node: usize) -> ArenaValues<'_, T, V, S> {
+        ArenaValues::new(&self.nodes, node)
+    }
+
+    /// 释放一个节点, 放入空闲列表
+    /// 调用者需要先将其从父节点中断开
+    pub fn free_node(&mut self, node: usize) {
+        debug_assert!(node != ROOT && self.nodes[node].can_remove());
+        self.free.push(node);
+    }
+
+    /// 清除所有节点, 保留竞技场已分配的空间
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[ROOT].value = None;
+        self.nodes[ROOT].childs.clear();
+        self.free.clear();
+    }
+}
+
+impl<T, V, S> Arena<T, V, S>
+where T: Hash + Eq,
+      S: BuildHasher,
+{
+    /// 查询末尾处节点的下标
+    /// 如果中途查询值串终止则返回`None`
+    pub fn query_node<Q>(&self, iter: impl IntoIterator<Item = Q>) -> Option<usize>
+    where Q: Borrow<T>
+    {
+        let mut node = ROOT;
+        for query in iter {
+            node = *self.nodes[node].childs.get(query.borrow())?;
+        }
+        Some(node)
+    }
+
+    /// 分配一个节点, 优先复用已被删除的节点
+    pub fn alloc_node(&mut self) -> usize
+    where S: Clone
+    {
+        if let Some(index) = self.free.pop() {
+            debug_assert!(self.nodes[index].can_remove());
+            return index;
+        }
+        let hasher = self.nodes[ROOT].childs.hasher().clone();
+        self.nodes.push(ArenaNode::with_hasher(hasher));
+        self.nodes.len() - 1
+    }
+
+    /// 获取子节点, 不存在时分配一个
+    pub fn get_or_insert_child(&mut self, node: usize, data: T) -> usize
+    where S: Clone
+    {
+        if let Some(&child) = self.nodes[node].childs.get(&data) {
+            return child;
+        }
+        let child = self.alloc_node();
+        self.nodes[node].childs.insert(data, child);
+        child
+    }
+
+    /// 沿着值串获取末尾处节点, 不存在的节点会被分配
+    pub fn get_or_insert_node(&mut self, iter: impl IntoIterator<Item = T>) -> usize
+    where S: Clone
+    {
+        iter.into_iter().fold(ROOT, |node, data| self.get_or_insert_child(node, data))
+    }
+
+    /// 取出值串末尾处节点的值, 并从下至上释放悬垂节点
+    pub fn remove<Q>(&mut self, iter: impl IntoIterator<Item = Q>) -> Option<V>
+    where Q: Borrow<T>
+    {
+        // 记录路径上的父节点以及查询值
+        let mut path = Vec::new();
+        let mut node = ROOT;
+        for query in iter {
+            let &child = self.nodes[node].childs.get(query.borrow())?;
+            path.push((node, query));
+            node = child;
+        }
+        let value = self.nodes[node].value.take()?;
+
+        // 从下至上清理悬垂节点
+        while let Some((parent, query)) = path.pop() {
+            if ! self.nodes[node].can_remove() {
+                break;
+            }
+            self.nodes[parent].childs.remove(query.borrow());
+            self.free.push(node);
+            node = parent;
+        }
+        Some(value)
+    }
+
+    /// 收缩每个哈希表以及空闲列表
+    pub fn shrink_to_fit(&mut self) {
+        for node in &mut self.nodes {
+            node.childs.shrink_to_fit();
+        }
+        self.free.shrink_to_fit();
+    }
+}
+
+/// 迭代竞技场中一个子树的值串以及值
+#[derive(Clone)]
+pub(crate) struct ArenaValues<'a, T, V, S> {
+    arena: &'a [ArenaNode<T, V, S>],
+    /// 存储每个节点以及其尚未产生的值
+    /// 当获取该值后将其设置为`None`
+    nodes: Vec<(Option<&'a V>, HashMapIter<'a, T, usize>)>,
+    /// 存储每个值
+    datas: Vec<&'a T>,
+}
+
+impl<'a, T, V, S> ArenaValues<'a, T, V, S> {
+    fn new(arena: &'a [ArenaNode<T, V, S>], node: usize) -> Self {
+        let node = &arena[node];
+        Self {
+            arena,
+            nodes: vec![(node.value.as_ref(), node.childs.iter())],
+            datas: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T, V, S> Iterator for ArenaValues<'a, T, V, S> {
+    type Item = (Vec<&'a T>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (value, kvs) = self.nodes.last_mut()?;
+            if let Some(value) = value.take() {
+                break Some((self.datas.clone(), value));
+            } else if let Some((data, &node)) = kvs.next() {
+                // 在末端迭代到下一个分支
+                let node = &self.arena[node];
+                self.datas.push(data);
+                self.nodes.push((node.value.as_ref(), node.childs.iter()));
+            } else {
+                // 末端迭代到了底部
+                self.datas.pop()?;
+                self.nodes.pop()?;
+            }
+        }
+    }
+}
+
+/// A TrieTree that stores all nodes in a `Vec`
+/// ===
+/// Children reference nodes by index, the slots of removed nodes are
+/// kept in a free list and reused by later insertions (with their `HashMap`
+/// capacity), so bulk building and dropping avoid many small allocations.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::ArenaTrieTree;
+/// let mut tree = ArenaTrieTree::new();
+/// assert!(tree.insert("abc".chars()));
+/// assert!(tree.insert("abd".chars()));
+/// assert!(tree.query("abc".chars()));
+/// assert_eq!(tree.node_count(), 5);
+/// assert!(tree.remove("abd".chars()));
+/// assert_eq!(tree.node_count(), 4);
+/// assert!(tree.insert("abe".chars()));
+/// assert_eq!(tree.node_count(), 5);
+/// ```
+#[derive(Clone)]
+pub struct ArenaTrieTree<T, S = RandomState> {
+    arena: Arena<T, (), S>,
+    count: usize,
+}
+
+impl<T> ArenaTrieTree<T>
+where T: Hash + Eq
+{
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T> Default for ArenaTrieTree<T>
+where T: Hash + Eq
+{
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<T, S> ArenaTrieTree<T, S> {
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::with_capacity_and_hasher(1, hasher)
+    }
+
+    /// new Self, reserve space for at least `capacity` nodes
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ArenaTrieTree;
+    /// # use std::collections::hash_map::RandomState;
+    /// let mut tree = ArenaTrieTree::with_capacity_and_hasher(16, RandomState::new());
+    /// assert!(tree.insert([1, 2, 3]));
+    /// ```
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        Self {
+            arena: Arena::with_capacity_and_hasher(capacity, hasher),
+            count: 0,
+        }
+    }
+
+    /// return values count
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// return the number of nodes in use, including the root
+    pub fn node_count(&self) -> usize {
+        self.arena.node_count()
+    }
+
+    /// return the number of nodes the arena can hold without reallocating,
+    /// the slots of removed nodes are included
+    pub fn capacity(&self) -> usize {
+        self.arena.capacity()
+    }
+
+    /// get iterator
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ArenaTrieTree;
+    /// let tree = ArenaTrieTree::from_iter(["abc".chars(), "bcd".chars()]);
+    /// let mut items: Vec<Vec<&char>> = tree.iter().collect();
+    /// items.sort();
+    /// assert_eq!(items, [[&'a', &'b', &'c'], [&'b', &'c', &'d']]);
+    /// ```
+    pub fn iter(&self) -> ArenaIter<'_, T, S> {
+        ArenaIter { values: self.arena.values(ROOT) }
+    }
+}
+
+impl<T, S> ArenaTrieTree<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
+{
+    /// clear all values<br/>
+    /// The nodes are dropped, but the allocated space of the arena is kept
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ArenaTrieTree;
+    /// let mut tree = ArenaTrieTree::from_iter(["abc".chars()]);
+    /// tree.clear();
+    /// assert_eq!(tree.count(), 0);
+    /// assert_eq!(tree.node_count(), 1);
+    /// assert!(! tree.query("abc".chars()));
+    /// ```
+    pub fn clear(&mut self) {
+        self.arena.clear();
+        self.count = 0;
+    }
+
+    /// insert values.
+    /// - return whether the insertion values was successful
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ArenaTrieTree;
+    /// let mut tree = ArenaTrieTree::new();
+    /// assert!(tree.insert("abc".chars()));
+    /// assert!(! tree.insert("abc".chars()));
+    /// assert_eq!(tree.count(), 1);
+    /// ```
+    pub fn insert(&mut self, iter: impl IntoIterator<Item = T>) -> bool
+    where S: Clone
+    {
+        let node = self.arena.get_or_insert_node(iter);
+        if self.arena[node].value.replace(()).is_some() {
+            // 已经是一个终节点, 插入失败
+            false
+        } else {
+            self.count += 1;
+            true
+        }
+    }
+
+    /// Whether the query values is in the tree
+    /// - see [`TrieTree::query`](crate::TrieTree::query)
+    pub fn query<Q>(&self, iter: impl IntoIterator<Item = Q>) -> bool
+    where Q: Borrow<T>
+    {
+        self.arena.query_node(iter)
+            .is_some_and(|node| self.arena[node].is_stop())
+    }
+
+    /// Whether the query values head is in the tree
+    /// - see [`TrieTree::query_nostop`](crate::TrieTree::query_nostop)
+    pub fn query_nostop<Q>(&self, iter: impl IntoIterator<Item = Q>) -> Option<bool>
+    where Q: Borrow<T>
+    {
+        self.arena.query_node(iter)
+            .map(|node| self.arena[node].is_stop())
+    }
+
+    /// Get the Iterator matching the prefix<br/>
+    /// If there is no matching value, return `None`
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ArenaTrieTree;
+    /// let tree = ArenaTrieTree::from_iter(["abc".chars(), "ace".chars()]);
+    /// let mut res: Vec<_> = tree.query_iter("a".chars()).unwrap().collect();
+    /// res.sort();
+    /// assert_eq!(res, [[&'b', &'c'], [&'c', &'e']]);
+    /// ```
+    pub fn query_iter<Q>(
+        &self,
+        iter: impl IntoIterator<Item = Q>,
+    ) -> Option<ArenaIter<'_, T, S>>
+    where Q: Borrow<T>
+    {
+        self.arena.query_node(iter)
+            .map(|node| ArenaIter { values: self.arena.values(node) })
+    }
+
+    /// remove values and clean up overhanging branches,
+    /// the removed nodes are put into the free list
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ArenaTrieTree;
+    /// let mut tree = ArenaTrieTree::from_iter(["abc".chars(), "a".chars()]);
+    /// assert!(tree.remove("abc".chars()));
+    /// assert!(! tree.remove("abc".chars()));
+    /// assert!(tree.query("a".chars()));
+    /// assert_eq!(tree.node_count(), 2);
+    /// ```
+    pub fn remove<Q>(&mut self, iter: impl IntoIterator<Item = Q>) -> bool
+    where Q: Borrow<T>
+    {
+        let removed = self.arena.remove(iter).is_some();
+        if removed {
+            self.count -= 1;
+        }
+        removed
+    }
+
+    /// Shrink each `HashMap` in the tree and the free list
+    pub fn shrink_to_fit(&mut self) {
+        self.arena.shrink_to_fit();
+    }
+}
+
+impl<T, I> FromIterator<I> for ArenaTrieTree<T>
+where T: Hash + Eq,
+      I: IntoIterator<Item = T>
+{
+    fn from_iter<IntoIter: IntoIterator<Item = I>>(iter: IntoIter) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T, S, I> Extend<I> for ArenaTrieTree<T, S>
+where T: Hash + Eq,
+      S: BuildHasher + Clone,
+      I: IntoIterator<Item = T>
+{
+    fn extend<IntoIter: IntoIterator<Item = I>>(&mut self, iter: IntoIter) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a ArenaTrieTree<T, S> {
+    type Item = Vec<&'a T>;
+    type IntoIter = ArenaIter<'a, T, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator used to iterate over `ArenaTrieTree`
+#[derive(Clone)]
+pub struct ArenaIter<'a, T, S = RandomState> {
+    values: ArenaValues<'a, T, (), S>,
+}
+
+impl<'a, T, S> Iterator for ArenaIter<'a, T, S> {
+    type Item = Vec<&'a T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.next().map(|(datas, ())| datas)
+    }
+}
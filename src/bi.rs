@@ -0,0 +1,327 @@
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash},
+};
+use alloc::vec::Vec;
+
+use crate::{collections::RandomState, trie_node::TrieNode, Iter, TrieTree};
+
+/// A TrieTree with a companion tree of the reversed values,
+/// both are kept in sync, so the values can be queried by their suffixes
+/// ===
+/// # Examples
+/// ```
+/// # use fast_trie_tree::BiTrie;
+/// let mut tree = BiTrie::new();
+/// assert!(tree.insert("reading".chars()));
+/// assert!(tree.insert("rest".chars()));
+/// assert!(tree.insert("sing".chars()));
+///
+/// let mut heads: Vec<String> = tree.query_suffix_iter("ing".chars())
+///     .unwrap()
+///     .map(String::from_iter)
+///     .collect();
+/// heads.sort();
+/// assert_eq!(heads, ["read", "s"]);
+///
+/// let res: Vec<String> = tree.prefix_and_suffix("re".chars(), "ing".chars())
+///     .into_iter()
+///     .map(String::from_iter)
+///     .collect();
+/// assert_eq!(res, ["reading"]);
+/// ```
+pub struct BiTrie<T, S = RandomState> {
+    forward: TrieTree<T, S>,
+    /// 反转后的值组成的树
+    reversed: TrieTree<T, S>,
+}
+
+impl<T> BiTrie<T>
+where T: Hash + Eq
+{
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T> Default for BiTrie<T>
+where T: Hash + Eq
+{
+    fn default() -> Self {
+        Self { forward: TrieTree::new(), reversed: TrieTree::new() }
+    }
+}
+
+impl<T, S> BiTrie<T, S>
+where T: Hash + Eq,
+      S: Clone,
+{
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            forward: TrieTree::with_hasher(hasher.clone()),
+            reversed: TrieTree::with_hasher(hasher),
+        }
+    }
+}
+
+impl<T, S> BiTrie<T, S> {
+    /// return values count
+    pub fn count(&self) -> usize {
+        self.forward.count()
+    }
+
+    /// get the tree of the values
+    pub fn as_forward(&self) -> &TrieTree<T, S> {
+        &self.forward
+    }
+
+    /// get the tree of the reversed values
+    pub fn as_reversed(&self) -> &TrieTree<T, S> {
+        &self.reversed
+    }
+}
+
+/// 沿着查询路径获取树中元素的引用
+fn query_path<T, S, Q>(
+    mut node: &TrieNode<T, S>,
+    iter: impl IntoIterator<Item = Q>,
+) -> Option<Vec<&T>>
+where T: Hash + Eq,
+      S: BuildHasher,
+      Q: Borrow<T>,
+{
+    let mut path = Vec::new();
+    for query in iter {
+        let (key, child) = node.childs().get_key_value(query.borrow())?;
+        path.push(key);
+        node = child;
+    }
+    Some(path)
+}
+
+impl<T, S> BiTrie<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
+{
+    /// clear all values
+    pub fn clear(&mut self)
+    where S: Clone
+    {
+        self.forward.clear();
+        self.reversed.clear();
+    }
+
+    /// insert values into both trees
+    /// - return whether the insertion values was successful
+    pub fn insert(&mut self, iter: impl IntoIterator<Item = T>) -> bool
+    where T: Clone,
+          S: Clone,
+    {
+        let values: Vec<T> = iter.into_iter().collect();
+        if ! self.forward.insert(values.iter().cloned()) {
+            return false;
+        }
+        let res = self.reversed.insert(values.into_iter().rev());
+        debug_assert!(res);
+        true
+    }
+
+    /// Whether the query values is in the tree
+    pub fn query<Q>(&self, iter: impl IntoIterator<Item = Q>) -> bool
+    where Q: Borrow<T>
+    {
+        self.forward.query(iter)
+    }
+
+    /// Get the Iterator matching the prefix, see [`TrieTree::query_iter`]
+    pub fn query_iter<Q>(&self, iter: impl IntoIterator<Item = Q>) -> Option<Iter<'_, T, S>>
+    where Q: Borrow<T>
+    {
+        self.forward.query_iter(iter)
+    }
+
+    /// Get the Iterator matching the suffix,
+    /// yield the values before the suffix in their original order<br/>
+    /// If there is no matching value, return `None`
+    pub fn query_suffix_iter<Q>(
+        &self,
+        iter: impl IntoIterator<Item = Q>,
+    ) -> Option<impl Iterator<Item = Vec<&T>>>
+    where Q: Borrow<T>
+    {
+        let suffix: Vec<Q> = iter.into_iter().collect();
+        let heads = self.reversed.query_iter(suffix.into_iter().rev())?;
+        Some(heads.map(|mut head| {
+            head.reverse();
+            head
+        }))
+    }
+
+    /// Get all values that start with the prefix and end with the suffix,
+    /// the prefix and the suffix may overlap<br/>
+    /// The values matching the prefix in the forward tree and the values
+    /// matching the suffix in the reversed tree are walked in turn,
+    /// only the smaller set is walked to the end and filtered
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::BiTrie;
+    /// let tree = BiTrie::from_iter([vec![1, 2, 1], vec![1, 2], vec![1, 3, 2, 1]]);
+    /// let mut res = tree.prefix_and_suffix([1, 2], [2, 1]);
+    /// assert_eq!(res, [[&1, &2, &1]]);
+    /// res = tree.prefix_and_suffix([1], [2, 1]);
+    /// res.sort();
+    /// assert_eq!(res, [vec![&1, &2, &1], vec![&1, &3, &2, &1]]);
+    /// ```
+    pub fn prefix_and_suffix<Q, R>(
+        &self,
+        prefix: impl IntoIterator<Item = Q>,
+        suffix: impl IntoIterator<Item = R>,
+    ) -> Vec<Vec<&T>>
+    where Q: Borrow<T>,
+          R: Borrow<T>,
+    {
+        let suffix: Vec<R> = suffix.into_iter().collect();
+        let Some(prefix) = query_path(&self.forward.root, prefix) else {
+            return Vec::new();
+        };
+        // 逆序树中的后缀路径, 顺序与值中相反
+        let Some(suffix) = query_path(&self.reversed.root, suffix.into_iter().rev()) else {
+            return Vec::new();
+        };
+        let (Some(mut tails), Some(mut heads)) = (
+            self.forward.query_iter(prefix.iter().copied()),
+            self.reversed.query_iter(suffix.iter().copied()),
+        ) else {
+            return Vec::new();
+        };
+
+        // 两个候选集合交替前进, 先耗尽的一方即为较小的集合
+        let mut candidates = Vec::new();
+        let mut reversed_candidates = Vec::new();
+        let forward_smaller = loop {
+            match tails.next() {
+                Some(tail) => candidates.push(tail),
+                None => break true,
+            }
+            match heads.next() {
+                Some(head) => reversed_candidates.push(head),
+                None => break false,
+            }
+        };
+
+        if forward_smaller {
+            // 候选值都以前缀开头, 检查其是否在逆序树的后缀之下
+            candidates.into_iter().filter_map(|tail| {
+                let mut values = prefix.clone();
+                values.extend(tail);
+                let ends_with = values.len() >= suffix.len()
+                    && values.iter().rev().zip(&suffix).all(|(a, b)| a == b);
+                ends_with.then_some(values)
+            }).collect()
+        } else {
+            // 候选值都以后缀结尾, 检查其是否在正向树的前缀之下
+            reversed_candidates.into_iter().filter_map(|head| {
+                let mut values = suffix.clone();
+                values.extend(head);
+                values.reverse();
+                let starts_with = values.len() >= prefix.len()
+                    && values.iter().zip(&prefix).all(|(a, b)| a == b);
+                starts_with.then_some(values)
+            }).collect()
+        }
+    }
+
+    /// remove values from both trees and clean up overhanging branches
+    pub fn remove<Q>(&mut self, iter: impl IntoIterator<Item = Q>) -> bool
+    where Q: Borrow<T>
+    {
+        let values: Vec<Q> = iter.into_iter().collect();
+        if ! self.forward.remove(values.iter().map(Borrow::<T>::borrow)) {
+            return false;
+        }
+        let res = self.reversed.remove(values.iter().rev().map(Borrow::<T>::borrow));
+        debug_assert!(res);
+        true
+    }
+
+    /// get iterator of the values
+    pub fn iter(&self) -> Iter<'_, T, S> {
+        self.forward.iter()
+    }
+
+    /// Shrink each `HashMap` in both trees
+    pub fn shrink_to_fit(&mut self) {
+        self.forward.shrink_to_fit();
+        self.reversed.shrink_to_fit();
+    }
+}
+
+impl<T, S> Clone for BiTrie<T, S>
+where T: Hash + Eq + Clone,
+      S: BuildHasher + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            forward: self.forward.clone(),
+            reversed: self.reversed.clone(),
+        }
+    }
+}
+
+impl<T: Debug, S> Debug for BiTrie<T, S>
+where T: Hash + Eq
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.forward.fmt(f)
+    }
+}
+
+impl<T, S> PartialEq for BiTrie<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.forward == other.forward
+    }
+}
+
+impl<T: Hash + Eq, S: BuildHasher> Eq for BiTrie<T, S> {}
+
+impl<T, I> FromIterator<I> for BiTrie<T>
+where T: Hash + Eq + Clone,
+      I: IntoIterator<Item = T>,
+{
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<T, S, I> Extend<I> for BiTrie<T, S>
+where T: Hash + Eq + Clone,
+      S: BuildHasher + Clone,
+      I: IntoIterator<Item = T>,
+{
+    fn extend<It: IntoIterator<Item = I>>(&mut self, iter: It) {
+        for values in iter {
+            self.insert(values);
+        }
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a BiTrie<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
+{
+    type Item = Vec<&'a T>;
+    type IntoIter = Iter<'a, T, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
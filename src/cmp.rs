@@ -0,0 +1,185 @@
+use core::{
+    cmp::Ordering,
+    hash::{BuildHasher, Hash, Hasher},
+};
+use alloc::{vec, vec::Vec};
+
+use crate::{trie_node::{ChildsIter, TrieNode}, TrieTree};
+
+/// FNV-1a 哈希器
+/// 相等的树可能使用不同种子的哈希构建器, 所以摘要需要使用固定的哈希算法
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn hash_one(value: impl Hash) -> u64 {
+        let mut hasher = Self::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// 正在计算摘要的节点
+struct Frame<'a, T, S> {
+    /// 该节点在父节点中的元素的哈希值
+    data_hash: u64,
+    node: &'a TrieNode<T, S>,
+    childs: ChildsIter<'a, T, S>,
+    /// 已计算的子节点摘要之和, 求和与子节点的顺序无关
+    sum: u64,
+}
+
+impl<'a, T, S> Frame<'a, T, S> {
+    fn new(data_hash: u64, node: &'a TrieNode<T, S>) -> Self {
+        Self { data_hash, node, childs: node.childs().iter(), sum: 0 }
+    }
+
+    fn digest(&self) -> u64 {
+        Fnv::hash_one((self.node.is_stop(), self.node.childs().len(), self.sum))
+    }
+}
+
+impl<T: Hash, S> TrieNode<T, S> {
+    /// 计算与子节点顺序无关的摘要
+    fn digest(&self) -> u64 {
+        // 后序遍历, 子节点的摘要先于父节点计算
+        let mut stack = vec![Frame::new(0, self)];
+        loop {
+            let frame = stack.last_mut().unwrap();
+            if let Some((data, child)) = frame.childs.next() {
+                stack.push(Frame::new(Fnv::hash_one(data), child));
+                continue;
+            }
+            let frame = stack.pop().unwrap();
+            let digest = frame.digest();
+            let Some(parent) = stack.last_mut() else {
+                break digest;
+            };
+            let entry = Fnv::hash_one((frame.data_hash, digest));
+            parent.sum = parent.sum.wrapping_add(entry);
+        }
+    }
+}
+
+impl<T: Hash, S> Hash for TrieNode<T, S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.digest());
+    }
+}
+
+impl<T: Hash, S> Hash for TrieTree<T, S> {
+    /// The hash does not depend on the iteration order of the children,
+    /// nor on the hasher builder, so it is consistent with `PartialEq`
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// # use std::collections::HashSet;
+    /// let a = TrieTree::from_iter(["ab".chars(), "cd".chars()]);
+    /// let b = TrieTree::from_iter(["cd".chars(), "ab".chars()]);
+    /// let set = HashSet::from([a, b]);
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.count.hash(state);
+        self.root.hash(state);
+    }
+}
+
+/// 按照元素升序前序遍历所有节点, 产生节点的路径以及是否为终节点
+struct SortedNodes<'a, T, S> {
+    root: Option<&'a TrieNode<T, S>>,
+    stack: Vec<vec::IntoIter<(&'a T, &'a TrieNode<T, S>)>>,
+    datas: Vec<&'a T>,
+}
+
+impl<'a, T: Ord, S> SortedNodes<'a, T, S> {
+    fn new(root: &'a TrieNode<T, S>) -> Self {
+        Self { root: Some(root), stack: Vec::new(), datas: Vec::new() }
+    }
+
+    /// 所有值的升序序列
+    fn values(root: &'a TrieNode<T, S>) -> impl Iterator<Item = Vec<&'a T>> {
+        Self::new(root).filter_map(|(datas, stop)| stop.then_some(datas))
+    }
+
+    /// 所有节点路径的升序序列
+    fn paths(root: &'a TrieNode<T, S>) -> impl Iterator<Item = Vec<&'a T>> {
+        Self::new(root).map(|(datas, _)| datas)
+    }
+
+    fn sorted_childs(node: &'a TrieNode<T, S>) -> vec::IntoIter<(&'a T, &'a TrieNode<T, S>)> {
+        let mut childs: Vec<_> = node.childs().iter().collect();
+        childs.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        childs.into_iter()
+    }
+}
+
+impl<'a, T: Ord, S> Iterator for SortedNodes<'a, T, S> {
+    type Item = (Vec<&'a T>, bool);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(root) = self.root.take() {
+            self.stack.push(Self::sorted_childs(root));
+            return Some((Vec::new(), root.is_stop()));
+        }
+        loop {
+            let childs = self.stack.last_mut()?;
+            if let Some((data, node)) = childs.next() {
+                self.datas.push(data);
+                self.stack.push(Self::sorted_childs(node));
+                break Some((self.datas.clone(), node.is_stop()));
+            }
+            self.stack.pop();
+            self.datas.pop();
+        }
+    }
+}
+
+impl<T, S> Ord for TrieTree<T, S>
+where T: Ord + Hash,
+      S: BuildHasher,
+{
+    /// Compare the sorted values sequences lexicographically,
+    /// a value is less than the values it is a prefix of
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// let a = TrieTree::from_iter(["ab".chars(), "b".chars()]);
+    /// let b = TrieTree::from_iter(["ab".chars(), "c".chars()]);
+    /// let c = TrieTree::from_iter(["abc".chars()]);
+    /// assert!(a < b);
+    /// assert!(b < c);
+    /// assert!(TrieTree::<char>::new() < a);
+    /// ```
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (&self.root, &other.root);
+        // 值相同时再比较所有节点, 使悬垂分支不同的树不相等, 与`PartialEq`一致
+        SortedNodes::values(a).cmp(SortedNodes::values(b))
+            .then_with(|| SortedNodes::paths(a).cmp(SortedNodes::paths(b)))
+    }
+}
+
+impl<T, S> PartialOrd for TrieTree<T, S>
+where T: Ord + Hash,
+      S: BuildHasher,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
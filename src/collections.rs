@@ -0,0 +1,15 @@
+//! 哈希表后端
+//! 启用`std`时使用标准库的哈希表, 否则使用`hashbrown`
+
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{
+    HashMap,
+    hash_map::{Iter as HashMapIter, RandomState},
+};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{
+    HashMap,
+    hash_map::Iter as HashMapIter,
+    DefaultHashBuilder as RandomState,
+};
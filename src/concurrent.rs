@@ -0,0 +1,299 @@
+use std::{
+    borrow::Borrow,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    },
+    vec::IntoIter as VecIntoIter,
+};
+
+use crate::{trie_node::TrieNode, TrieTree};
+
+/// default shards count
+const DEFAULT_SHARDS: usize = 16;
+
+/// A thread-safe TrieTree, all operations use `&self`
+/// ===
+/// The children of the root are sharded by the hash of the first element,
+/// each shard is protected by its own `RwLock`.
+/// Writers only lock the shard of the value they are modifying,
+/// so readers and writers on other shards are never blocked.
+///
+/// Linearizability
+/// ---
+/// * `insert`, `remove`, `query` and `query_nostop` are linearizable,
+///   each takes effect at the moment it holds the lock of its shard
+/// * `count` is updated before the shard lock is released,
+///   so once a query observes a value, `count` observes it too
+/// * `query_iter` with a non-empty prefix locks only one shard,
+///   `iter`, `snapshot` and `query_iter` with an empty prefix
+///   hold the read locks of all shards at the same time,
+///   so they return a consistent view of the whole tree
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::ConcurrentTrie;
+/// let tree = ConcurrentTrie::new();
+/// std::thread::scope(|s| {
+///     s.spawn(|| assert!(tree.insert("abc".chars())));
+///     s.spawn(|| assert!(tree.insert("bcd".chars())));
+/// });
+/// assert_eq!(tree.count(), 2);
+/// assert!(tree.query("abc".chars()));
+/// assert!(tree.query("bcd".chars()));
+/// ```
+pub struct ConcurrentTrie<T> {
+    shards: Box<[RwLock<TrieNode<T>>]>,
+    hasher: RandomState,
+    count: AtomicUsize,
+}
+
+impl<T> Default for ConcurrentTrie<T> {
+    fn default() -> Self {
+        Self::with_shards(DEFAULT_SHARDS)
+    }
+}
+
+impl<T> ConcurrentTrie<T> {
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// new Self with the specified number of shards
+    /// # Panics
+    /// - if `shards` is zero
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ConcurrentTrie;
+    /// let tree = ConcurrentTrie::with_shards(4);
+    /// assert!(tree.insert([1, 2, 3]));
+    /// assert!(tree.query([1, 2, 3]));
+    /// ```
+    pub fn with_shards(shards: usize) -> Self {
+        assert_ne!(shards, 0, "shards count cannot be zero");
+        Self {
+            shards: (0..shards)
+                .map(|_| RwLock::new(TrieNode::default()))
+                .collect(),
+            hasher: RandomState::new(),
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    /// return values count
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// 读锁定指定分片
+    /// 在其它线程panic时依旧可以访问, 节点结构在panic后依旧是完整的
+    fn read_shard(&self, index: usize) -> RwLockReadGuard<'_, TrieNode<T>> {
+        self.shards[index].read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 写锁定指定分片
+    fn write_shard(&self, index: usize) -> RwLockWriteGuard<'_, TrieNode<T>> {
+        self.shards[index].write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 按顺序读锁定所有分片
+    fn read_all(&self) -> Vec<RwLockReadGuard<'_, TrieNode<T>>> {
+        (0..self.shards.len())
+            .map(|i| self.read_shard(i))
+            .collect()
+    }
+}
+
+impl<T> ConcurrentTrie<T>
+where T: Hash + Eq
+{
+    /// 获取值串所在的分片, 空值串位于第零个分片
+    fn shard_of<Q>(&self, first: Option<&Q>) -> usize
+    where Q: Borrow<T>
+    {
+        first.map_or(0, |first| {
+            self.hasher.hash_one(first.borrow()) as usize % self.shards.len()
+        })
+    }
+
+    /// insert values.
+    /// - return whether the insertion values was successful
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ConcurrentTrie;
+    /// let tree = ConcurrentTrie::new();
+    /// assert!(tree.insert("abc".chars()));
+    /// assert!(! tree.insert("abc".chars()));
+    /// assert_eq!(tree.count(), 1);
+    /// ```
+    pub fn insert(&self, iter: impl IntoIterator<Item = T>) -> bool {
+        let mut iter = iter.into_iter().peekable();
+        let mut shard = self.write_shard(self.shard_of(iter.peek()));
+        let res = shard.insert(iter);
+        if res {
+            self.count.fetch_add(1, Ordering::Release);
+        }
+        res
+    }
+
+    /// remove values and clean up overhanging branches
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ConcurrentTrie;
+    /// let tree = ConcurrentTrie::new();
+    /// assert!(tree.insert("abc".chars()));
+    /// assert!(tree.remove("abc".chars()));
+    /// assert!(! tree.remove("abc".chars()));
+    /// assert_eq!(tree.count(), 0);
+    /// ```
+    pub fn remove<Q>(&self, iter: impl IntoIterator<Item = Q>) -> bool
+    where Q: Borrow<T>
+    {
+        let mut iter = iter.into_iter().peekable();
+        let mut shard = self.write_shard(self.shard_of(iter.peek()));
+        let res = shard.remove_branch(iter);
+        if res {
+            self.count.fetch_sub(1, Ordering::Release);
+        }
+        res
+    }
+
+    /// Whether the query values is in the tree
+    /// - see [`TrieTree::query`]
+    pub fn query<Q>(&self, iter: impl IntoIterator<Item = Q>) -> bool
+    where Q: Borrow<T>
+    {
+        let mut iter = iter.into_iter().peekable();
+        self.read_shard(self.shard_of(iter.peek())).query(iter)
+    }
+
+    /// Whether the query values head is in the tree
+    /// - see [`TrieTree::query_nostop`]
+    pub fn query_nostop<Q>(&self, iter: impl IntoIterator<Item = Q>) -> Option<bool>
+    where Q: Borrow<T>
+    {
+        let mut iter = iter.into_iter().peekable();
+        let first = iter.peek();
+        if first.is_none() {
+            // 空前缀总是存在
+            return Some(self.read_shard(0).is_stop());
+        }
+        self.read_shard(self.shard_of(first)).query_nostop(iter)
+    }
+
+    /// clear all values
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ConcurrentTrie;
+    /// let tree = ConcurrentTrie::from_iter(["abc".chars(), "".chars()]);
+    /// assert_eq!(tree.count(), 2);
+    /// tree.clear();
+    /// assert_eq!(tree.count(), 0);
+    /// assert!(! tree.query("abc".chars()));
+    /// ```
+    pub fn clear(&self) {
+        let mut shards: Vec<_> = (0..self.shards.len())
+            .map(|i| self.write_shard(i))
+            .collect();
+        for shard in &mut shards {
+            **shard = TrieNode::default();
+        }
+        self.count.store(0, Ordering::Release);
+    }
+}
+
+impl<T> ConcurrentTrie<T>
+where T: Hash + Eq + Clone
+{
+    /// Get the owned values matching the prefix<br/>
+    /// If there is no matching value, return `None`
+    /// - the values are collected while holding the read lock
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ConcurrentTrie;
+    /// let tree = ConcurrentTrie::from_iter(["abc".chars(), "ace".chars()]);
+    /// let mut res: Vec<_> = tree.query_iter("a".chars()).unwrap().collect();
+    /// res.sort();
+    /// assert_eq!(res, [['b', 'c'], ['c', 'e']]);
+    /// assert!(tree.query_iter("b".chars()).is_none());
+    /// ```
+    pub fn query_iter<Q>(
+        &self,
+        iter: impl IntoIterator<Item = Q>,
+    ) -> Option<VecIntoIter<Vec<T>>>
+    where Q: Borrow<T>
+    {
+        let mut iter = iter.into_iter().peekable();
+        if iter.peek().is_none() {
+            return Some(self.iter());
+        }
+        let shard = self.read_shard(self.shard_of(iter.peek()));
+        let res = shard.query_iter(iter)?
+            .map(|values| values.into_iter().cloned().collect())
+            .collect::<Vec<_>>();
+        Some(res.into_iter())
+    }
+
+    /// get owned values iterator
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::ConcurrentTrie;
+    /// let tree = ConcurrentTrie::from_iter([[1, 2], [2, 3]]);
+    /// let mut res: Vec<_> = tree.iter().collect();
+    /// res.sort();
+    /// assert_eq!(res, [[1, 2], [2, 3]]);
+    /// ```
+    pub fn iter(&self) -> VecIntoIter<Vec<T>> {
+        let shards = self.read_all();
+        let mut res = Vec::with_capacity(self.count());
+        for shard in &shards {
+            res.extend(shard.iter()
+                .map(|values| values.into_iter().cloned().collect()));
+        }
+        res.into_iter()
+    }
+
+    /// Clone a consistent snapshot into a [`TrieTree`]
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{ConcurrentTrie, TrieTree};
+    /// let tree = ConcurrentTrie::from_iter(["abc".chars(), "ace".chars()]);
+    /// assert_eq!(tree.snapshot(),
+    ///            TrieTree::from_iter(["abc".chars(), "ace".chars()]));
+    /// ```
+    pub fn snapshot(&self) -> TrieTree<T> {
+        let shards = self.read_all();
+        let mut root = TrieNode::default();
+        if shards[0].is_stop() {
+            root.set_stop();
+        }
+        for shard in &shards {
+            root.childs_mut().extend(shard.childs()
+                .iter()
+                .map(|(data, node)| (data.clone(), node.clone())));
+        }
+        TrieTree {
+            root,
+            count: self.count(),
+        }
+    }
+}
+
+impl<T, I> FromIterator<I> for ConcurrentTrie<T>
+where T: Hash + Eq,
+      I: IntoIterator<Item = T>
+{
+    fn from_iter<IntoIter: IntoIterator<Item = I>>(iter: IntoIter) -> Self {
+        let tree = Self::new();
+        for item in iter {
+            tree.insert(item);
+        }
+        tree
+    }
+}
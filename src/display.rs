@@ -0,0 +1,300 @@
+use core::{
+    cmp::Ordering,
+    fmt::{self, Debug, Display, Write},
+};
+use alloc::{vec, vec::Vec};
+
+use crate::{trie_node::TrieNode, TrieTree};
+
+const INDENT: &str = "    ";
+const MORE: &str = "\u{2026}";
+
+/// Layout of [`TrieDisplay`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayStyle {
+    /// Nested braces, same as the alternate `Debug` output
+    #[default]
+    Nested,
+    /// Tree drawing lines, such as `├── ('a')`
+    Tree,
+}
+
+/// Configurable renderer of a [`TrieTree`], see [`TrieTree::display`]
+///
+/// Without sorting, the children are rendered in `HashMap` order,
+/// which may change between runs
+pub struct TrieDisplay<'a, T, S> {
+    root: &'a TrieNode<T, S>,
+    compare: Option<fn(&T, &T) -> Ordering>,
+    max_depth: Option<usize>,
+    max_children: Option<usize>,
+    style: DisplayStyle,
+}
+
+impl<T, S> Clone for TrieDisplay<'_, T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, S> Copy for TrieDisplay<'_, T, S> {}
+
+impl<T, S> TrieTree<T, S> {
+    /// Get a configurable renderer of the tree
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, DisplayStyle};
+    /// let tree = TrieTree::from_iter(["ab".chars(), "ac".chars(), "d".chars()]);
+    /// assert_eq!(tree.display().sorted().to_string(), concat!(
+    ///     "(/): {\n",
+    ///     "    ('a'): {\n",
+    ///     "        ['b']: {}, \n",
+    ///     "        ['c']: {}\n",
+    ///     "    }, \n",
+    ///     "    ['d']: {}\n",
+    ///     "}",
+    /// ));
+    /// assert_eq!(tree.display()
+    ///            .sorted()
+    ///            .style(DisplayStyle::Tree)
+    ///            .max_children(1)
+    ///            .to_string(), concat!(
+    ///     "(/)\n",
+    ///     "├── ('a')\n",
+    ///     "│   ├── ['b']\n",
+    ///     "│   └── … 1 more\n",
+    ///     "└── … 1 more\n",
+    /// ));
+    /// ```
+    pub fn display(&self) -> TrieDisplay<'_, T, S> {
+        TrieDisplay {
+            root: &self.root,
+            compare: None,
+            max_depth: None,
+            max_children: None,
+            style: DisplayStyle::default(),
+        }
+    }
+}
+
+impl<'a, T, S> TrieDisplay<'a, T, S> {
+    /// Render the children in the order of the compare function
+    pub fn sort_by(mut self, compare: fn(&T, &T) -> Ordering) -> Self {
+        self.compare = Some(compare);
+        self
+    }
+
+    /// Render the children in ascending order, so the output is deterministic
+    pub fn sorted(self) -> Self
+    where T: Ord
+    {
+        self.sort_by(T::cmp)
+    }
+
+    /// Do not render the nodes deeper than `depth`, the root depth is zero
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// let tree = TrieTree::from_iter(["abc".chars()]);
+    /// assert_eq!(tree.display().max_depth(1).to_string(), concat!(
+    ///     "(/): {\n",
+    ///     "    ('a'): {\n",
+    ///     "        … 1 more\n",
+    ///     "    }\n",
+    ///     "}",
+    /// ));
+    /// ```
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Render at most `count` children of each node,
+    /// the others are elided as `… N more`
+    pub fn max_children(mut self, count: usize) -> Self {
+        self.max_children = Some(count);
+        self
+    }
+
+    /// Set the layout
+    pub fn style(mut self, style: DisplayStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// 获取一个节点需要渲染的子项
+    fn items(&self, node: &'a TrieNode<T, S>, depth: usize) -> Vec<Item<'a, T, S>> {
+        let len = node.childs().len();
+        if len == 0 {
+            return Vec::new();
+        }
+        if self.max_depth.is_some_and(|max| depth >= max) {
+            return vec![Item::More(len)];
+        }
+        let mut items: Vec<_> = node.childs().iter()
+            .map(|(data, node)| Item::Node(data, node))
+            .collect();
+        if let Some(compare) = self.compare {
+            items.sort_unstable_by(|a, b| match (a, b) {
+                (Item::Node(a, _), Item::Node(b, _)) => compare(a, b),
+                _ => unreachable!(),
+            });
+        }
+        if let Some(max) = self.max_children.filter(|&max| max < len) {
+            items.truncate(max);
+            items.push(Item::More(len - max));
+        }
+        items
+    }
+}
+
+/// 一个需要渲染的子项
+enum Item<'a, T, S> {
+    Node(&'a T, &'a TrieNode<T, S>),
+    /// 被省略的子节点数量
+    More(usize),
+}
+
+impl<T, S> Clone for Item<'_, T, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, S> Copy for Item<'_, T, S> {}
+
+/// 正在渲染子项的节点
+struct Frame<'a, T, S> {
+    items: Vec<Item<'a, T, S>>,
+    pos: usize,
+    /// 该节点是否为其父节点的最后一个子项
+    is_last: bool,
+}
+
+impl<'a, T, S> Frame<'a, T, S> {
+    fn new(items: Vec<Item<'a, T, S>>, is_last: bool) -> Self {
+        Self { items, pos: 0, is_last }
+    }
+
+    fn next(&mut self) -> Option<(Item<'a, T, S>, bool)> {
+        let item = *self.items.get(self.pos)?;
+        self.pos += 1;
+        Some((item, self.pos == self.items.len()))
+    }
+}
+
+/// 写入节点标签, 使用与`Debug`相同的`[x]`与`(x)`表示是否为终节点
+fn write_label<T, S>(
+    f: &mut fmt::Formatter<'_>,
+    node: &TrieNode<T, S>,
+    data: Option<&T>,
+) -> fmt::Result
+where T: Debug
+{
+    let (open, close) = if node.is_stop() { ('[', ']') } else { ('(', ')') };
+    f.write_char(open)?;
+    match data {
+        Some(data) => write!(f, "{data:?}")?,
+        None => f.write_char('/')?,
+    }
+    f.write_char(close)
+}
+
+fn write_indent(f: &mut fmt::Formatter<'_>, level: usize) -> fmt::Result {
+    for _ in 0..level {
+        f.write_str(INDENT)?;
+    }
+    Ok(())
+}
+
+impl<T, S> TrieDisplay<'_, T, S>
+where T: Debug
+{
+    fn fmt_nested(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_label(f, self.root, None)?;
+        f.write_str(": {")?;
+        let items = self.items(self.root, 0);
+        if items.is_empty() {
+            return f.write_char('}');
+        }
+        f.write_char('\n')?;
+        let mut stack = vec![Frame::new(items, true)];
+        while ! stack.is_empty() {
+            let level = stack.len();
+            let frame = stack.last_mut().unwrap();
+            let Some((item, is_last)) = frame.next() else {
+                stack.pop();
+                write_indent(f, level - 1)?;
+                f.write_char('}')?;
+                if let Some(parent) = stack.last() {
+                    f.write_str(if parent.pos == parent.items.len() { "\n" } else { ", \n" })?;
+                }
+                continue;
+            };
+            write_indent(f, level)?;
+            let sep = if is_last { "\n" } else { ", \n" };
+            match item {
+                Item::Node(data, node) => {
+                    write_label(f, node, Some(data))?;
+                    f.write_str(": {")?;
+                    let items = self.items(node, level);
+                    if items.is_empty() {
+                        f.write_char('}')?;
+                        f.write_str(sep)?;
+                    } else {
+                        f.write_char('\n')?;
+                        stack.push(Frame::new(items, is_last));
+                    }
+                },
+                Item::More(count) => {
+                    write!(f, "{MORE} {count} more")?;
+                    f.write_str(sep)?;
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt_tree(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_label(f, self.root, None)?;
+        f.write_char('\n')?;
+        let mut stack = vec![Frame::new(self.items(self.root, 0), true)];
+        while ! stack.is_empty() {
+            let level = stack.len();
+            let Some((item, is_last)) = stack.last_mut().unwrap().next() else {
+                stack.pop();
+                continue;
+            };
+            // 根节点的帧不需要前缀
+            for frame in &stack[1..] {
+                f.write_str(if frame.is_last { "    " } else { "\u{2502}   " })?;
+            }
+            f.write_str(if is_last { "\u{2514}\u{2500}\u{2500} " } else { "\u{251c}\u{2500}\u{2500} " })?;
+            match item {
+                Item::Node(data, node) => {
+                    write_label(f, node, Some(data))?;
+                    f.write_char('\n')?;
+                    let items = self.items(node, level);
+                    if ! items.is_empty() {
+                        stack.push(Frame::new(items, is_last));
+                    }
+                },
+                Item::More(count) => {
+                    writeln!(f, "{MORE} {count} more")?;
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T, S> Display for TrieDisplay<'_, T, S>
+where T: Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.style {
+            DisplayStyle::Nested => self.fmt_nested(f),
+            DisplayStyle::Tree => self.fmt_tree(f),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug, Write},
+    hash::{BuildHasher, Hash},
+};
+use alloc::collections::VecDeque;
+
+use crate::{trie_node::TrieNode, TrieTree};
+
+/// Options of [`TrieTree::to_dot_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DotOptions {
+    max_depth: Option<usize>,
+    max_nodes: Option<usize>,
+}
+
+impl DotOptions {
+    /// new Self, without any limit
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Do not export the nodes deeper than `depth`, the root depth is zero
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Export at most `count` nodes, including the root
+    pub fn max_nodes(mut self, count: usize) -> Self {
+        self.max_nodes = Some(count);
+        self
+    }
+}
+
+/// 转义写入双引号字符串中的内容
+struct Escape<'a, W: ?Sized>(&'a mut W);
+
+impl<W: Write + ?Sized> Write for Escape<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.chars() {
+            match ch {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                _ => self.0.write_char(ch)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 将`io::Write`适配为`fmt::Write`, 并保存写入时产生的错误
+#[cfg(feature = "std")]
+struct IoAdapter<'a, W: ?Sized> {
+    inner: &'a mut W,
+    error: std::io::Result<()>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|e| {
+            self.error = Err(e);
+            fmt::Error
+        })
+    }
+}
+
+/// 写入一个节点, 使用与`Debug`相同的`[x]`与`(x)`表示是否为终节点
+fn write_node<T, S, W>(
+    w: &mut W,
+    id: usize,
+    node: &TrieNode<T, S>,
+    data: Option<&T>,
+) -> fmt::Result
+where T: Debug,
+      W: Write + ?Sized,
+{
+    let (open, close, style) = if node.is_stop() {
+        ('[', ']', "shape=box, style=bold")
+    } else {
+        ('(', ')', "shape=ellipse")
+    };
+    write!(w, "    n{id} [label=\"{open}")?;
+    match data {
+        Some(data) => write!(Escape(w), "{data:?}")?,
+        None => w.write_char('/')?,
+    }
+    writeln!(w, "{close}\", {style}];")
+}
+
+/// 按照广度优先导出子树, 超出限制的子节点使用一个省略节点代替
+fn write_dot<T, S, W>(
+    root: &TrieNode<T, S>,
+    w: &mut W,
+    options: &DotOptions,
+) -> fmt::Result
+where T: Debug,
+      W: Write + ?Sized,
+{
+    let max_nodes = options.max_nodes.unwrap_or(usize::MAX).max(1);
+    let max_depth = options.max_depth.unwrap_or(usize::MAX);
+
+    writeln!(w, "digraph TrieTree {{")?;
+    let mut next_id = 1;
+    // 节点, 节点的键, 节点编号, 深度
+    let mut queue = VecDeque::from([(root, None, 0, 0)]);
+    while let Some((node, data, id, depth)) = queue.pop_front() {
+        write_node(w, id, node, data)?;
+        let mut skipped = 0;
+        for (data, child) in node.childs() {
+            if depth >= max_depth || next_id >= max_nodes {
+                skipped += 1;
+                continue;
+            }
+            writeln!(w, "    n{id} -> n{next_id};")?;
+            queue.push_back((child, Some(data), next_id, depth + 1));
+            next_id += 1;
+        }
+        if skipped != 0 {
+            writeln!(w, "    n{id}_more [label=\"... {skipped} more\", shape=plaintext];")?;
+            writeln!(w, "    n{id} -> n{id}_more [style=dashed];")?;
+        }
+    }
+    writeln!(w, "}}")
+}
+
+impl<T, S> TrieTree<T, S>
+where T: Debug,
+{
+    /// Export the tree as a Graphviz DOT graph
+    /// - stop nodes are drawn as bold boxes labeled `[x]`,
+    ///   other nodes are drawn as ellipses labeled `(x)`
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// let tree = TrieTree::from_iter(["ab".chars()]);
+    /// let mut dot = String::new();
+    /// tree.to_dot(&mut dot).unwrap();
+    /// assert_eq!(dot, concat!(
+    ///     "digraph TrieTree {\n",
+    ///     "    n0 [label=\"(/)\", shape=ellipse];\n",
+    ///     "    n0 -> n1;\n",
+    ///     "    n1 [label=\"('a')\", shape=ellipse];\n",
+    ///     "    n1 -> n2;\n",
+    ///     "    n2 [label=\"['b']\", shape=box, style=bold];\n",
+    ///     "}\n",
+    /// ));
+    /// ```
+    pub fn to_dot<W>(&self, w: &mut W) -> fmt::Result
+    where W: Write + ?Sized,
+    {
+        self.to_dot_with(w, &DotOptions::new())
+    }
+
+    /// Export the tree as a Graphviz DOT graph, with depth and nodes limits
+    /// - the children that are not exported are replaced by a `... N more` node
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, DotOptions};
+    /// let tree = TrieTree::from_iter(["abc".chars(), "ad".chars()]);
+    /// let mut dot = String::new();
+    /// tree.to_dot_with(&mut dot, &DotOptions::new().max_depth(1)).unwrap();
+    /// assert!(dot.contains("n1 [label=\"('a')\", shape=ellipse];"));
+    /// assert!(dot.contains("n1_more [label=\"... 2 more\", shape=plaintext];"));
+    /// assert!(! dot.contains("'b'"));
+    /// ```
+    pub fn to_dot_with<W>(&self, w: &mut W, options: &DotOptions) -> fmt::Result
+    where W: Write + ?Sized,
+    {
+        write_dot(&self.root, w, options)
+    }
+
+    /// Export the tree as a Graphviz DOT graph into an [`io::Write`](std::io::Write),
+    /// such as a file, with depth and nodes limits
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, DotOptions};
+    /// let tree = TrieTree::from_iter(["ab".chars()]);
+    /// let mut dot = Vec::new();
+    /// tree.to_dot_io(&mut dot, &DotOptions::new()).unwrap();
+    /// assert!(dot.starts_with(b"digraph TrieTree {\n"));
+    /// assert!(dot.ends_with(b"}\n"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_dot_io<W>(&self, w: &mut W, options: &DotOptions) -> std::io::Result<()>
+    where W: std::io::Write + ?Sized,
+    {
+        let mut adapter = IoAdapter { inner: w, error: Ok(()) };
+        match write_dot(&self.root, &mut adapter, options) {
+            Ok(()) => Ok(()),
+            Err(fmt::Error) => adapter.error.and(Err(std::io::Error::other("formatter error"))),
+        }
+    }
+}
+
+impl<T, S> TrieTree<T, S>
+where T: Hash + Eq + Debug,
+      S: BuildHasher,
+{
+    /// Export the subtree matching the prefix as a Graphviz DOT graph<br/>
+    /// If there is no matching prefix, return `None`
+    /// - the root of the subtree is labeled `/`
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, DotOptions};
+    /// let tree = TrieTree::from_iter(["abc".chars(), "bcd".chars()]);
+    /// let mut dot = String::new();
+    /// tree.query_to_dot("ab".chars(), &mut dot, &DotOptions::new())
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!(dot.contains("['c']"));
+    /// assert!(! dot.contains("'d'"));
+    /// assert!(tree.query_to_dot("x".chars(), &mut dot, &DotOptions::new()).is_none());
+    /// ```
+    pub fn query_to_dot<Q, W>(
+        &self,
+        iter: impl IntoIterator<Item = Q>,
+        w: &mut W,
+        options: &DotOptions,
+    ) -> Option<fmt::Result>
+    where Q: Borrow<T>,
+          W: Write + ?Sized,
+    {
+        let node = self.root.query_node(iter.into_iter())?;
+        Some(write_dot(node, w, options))
+    }
+}
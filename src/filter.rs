@@ -0,0 +1,443 @@
+use core::{hash::BuildHasher, ops::Range};
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    arena::{Arena, ROOT},
+    collections::RandomState,
+    trie_node::TrieNode,
+    TrieTree,
+};
+
+/// A match of a word, by byte offsets in a text,
+/// or by element indices in a sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Match {
+    /// the offset of the first element
+    pub start: usize,
+    /// the offset after the last element
+    pub end: usize,
+}
+
+impl Match {
+    /// get the range of the match
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Which word to take when several words start at the leftmost position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MatchKind {
+    /// take the longest word
+    #[default]
+    LeftmostLongest,
+    /// take the shortest word
+    LeftmostShortest,
+    /// take the word inserted first, the insertion order is recorded by
+    /// [`WordFilter`]<br/>
+    /// A [`TrieTree`] does not record the insertion order,
+    /// so it is the same as `LeftmostShortest` for [`TrieTree::find_all`]
+    LeftmostFirst,
+}
+
+/// Options of [`TrieTree::find_all`] and [`WordFilter::find_all`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MatchOptions {
+    kind: MatchKind,
+    overlapping: bool,
+    skip: Option<fn(char) -> bool>,
+}
+
+impl MatchOptions {
+    /// new Self, non-overlapping leftmost-longest matches without skipping
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the match kind of non-overlapping matches
+    pub fn kind(mut self, kind: MatchKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Report all matches, including the overlapping ones,
+    /// ordered by start then end, the match kind is ignored
+    pub fn overlapping(mut self, overlapping: bool) -> Self {
+        self.overlapping = overlapping;
+        self
+    }
+
+    /// Ignore the `char`s inside words for which `skip` returns true,
+    /// such as spaces and punctuations, a match never starts with them
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, MatchOptions};
+    /// let tree = TrieTree::from_iter(["bad".chars()]);
+    /// let options = MatchOptions::new().skip(|ch| ch.is_whitespace() || ch == '.');
+    /// assert_eq!(tree.mask("so b.a d", options, '*'), "so *****");
+    /// ```
+    pub fn skip(mut self, skip: fn(char) -> bool) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    fn is_skip(&self, ch: char) -> bool {
+        self.skip.is_some_and(|skip| skip(ch))
+    }
+}
+
+/// 查找所用的树, 只有`WordFilter`记录了词的插入顺序
+enum Words<'a, S> {
+    Tree(&'a TrieNode<char, S>),
+    Ordered(&'a Arena<char, usize, S>),
+}
+
+impl<S> Clone for Words<'_, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S> Copy for Words<'_, S> {}
+
+/// 查找时所在的节点
+enum Cursor<'a, S> {
+    Tree(&'a TrieNode<char, S>),
+    Ordered(usize),
+}
+
+impl<'a, S: BuildHasher> Words<'a, S> {
+    fn root(self) -> Cursor<'a, S> {
+        match self {
+            Self::Tree(root) => Cursor::Tree(root),
+            Self::Ordered(_) => Cursor::Ordered(ROOT),
+        }
+    }
+
+    fn child(self, cursor: Cursor<'a, S>, ch: char) -> Option<Cursor<'a, S>> {
+        match (self, cursor) {
+            (_, Cursor::Tree(node)) => node.get_child(ch).map(Cursor::Tree),
+            (Self::Ordered(arena), Cursor::Ordered(node)) => {
+                arena[node].childs.get(&ch).copied().map(Cursor::Ordered)
+            },
+            (Self::Tree(_), Cursor::Ordered(_)) => unreachable!(),
+        }
+    }
+
+    /// 在该节点结束的词的插入序号, 未记录顺序时都为零
+    fn order(self, cursor: &Cursor<'a, S>) -> Option<usize> {
+        match (self, cursor) {
+            (_, Cursor::Tree(node)) => node.is_stop().then_some(0),
+            (Self::Ordered(arena), &Cursor::Ordered(node)) => arena[node].value,
+            (Self::Tree(_), Cursor::Ordered(_)) => unreachable!(),
+        }
+    }
+}
+
+/// Iterator of the matches in a text, see [`TrieTree::find_all`]
+/// and [`WordFilter::find_all`]
+pub struct FindIter<'a, S = RandomState> {
+    words: Words<'a, S>,
+    text: &'a str,
+    options: MatchOptions,
+    /// 下一个匹配的起始字节偏移
+    pos: usize,
+    /// 当前起始位置尚未产生的匹配, 逆序存储, 非重叠匹配时只保留一个候选
+    pending: Vec<Match>,
+}
+
+impl<S> Clone for FindIter<'_, S> {
+    fn clone(&self) -> Self {
+        Self { pending: self.pending.clone(), ..*self }
+    }
+}
+
+impl<S: BuildHasher> Iterator for FindIter<'_, S> {
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.pending.pop() {
+                return Some(found);
+            }
+            let start = self.pos;
+            let rest = &self.text[start..];
+            let first = rest.chars().next()?;
+            self.pos += first.len_utf8();
+            if self.options.is_skip(first) {
+                continue;
+            }
+            let mut node = self.words.root();
+            // 非重叠匹配时当前候选的插入序号
+            let mut best_order = usize::MAX;
+            for (i, ch) in rest.char_indices() {
+                if i != 0 && self.options.is_skip(ch) {
+                    continue;
+                }
+                let Some(child) = self.words.child(node, ch) else { break };
+                node = child;
+                let Some(order) = self.words.order(&node) else { continue };
+                let found = Match { start, end: start + i + ch.len_utf8() };
+                if self.options.overlapping {
+                    self.pending.push(found);
+                    continue;
+                }
+                match self.options.kind {
+                    MatchKind::LeftmostLongest => {
+                        self.pending.clear();
+                        self.pending.push(found);
+                    },
+                    MatchKind::LeftmostShortest => {
+                        self.pending.push(found);
+                        break;
+                    },
+                    MatchKind::LeftmostFirst => if order < best_order {
+                        best_order = order;
+                        self.pending.clear();
+                        self.pending.push(found);
+                    },
+                }
+            }
+            if self.options.overlapping {
+                self.pending.reverse();
+            } else if let Some(found) = self.pending.pop() {
+                self.pos = found.end;
+                return Some(found);
+            }
+        }
+    }
+}
+
+/// 替换匹配, 与上一个被替换的匹配重叠的匹配不被替换
+fn replace_matches<S, F, R>(text: &str, matches: FindIter<'_, S>, mut f: F) -> String
+where S: BuildHasher,
+      F: FnMut(Match) -> R,
+      R: AsRef<str>,
+{
+    let mut res = String::with_capacity(text.len());
+    let mut last = 0;
+    for found in matches {
+        if found.start < last {
+            continue;
+        }
+        res.push_str(&text[last..found.start]);
+        res.push_str(f(found).as_ref());
+        last = found.end;
+    }
+    res.push_str(&text[last..]);
+    res
+}
+
+/// 将被任意匹配覆盖的字符替换为`mask`
+fn mask_matches<S: BuildHasher>(text: &str, matches: FindIter<'_, S>, mask: char) -> String {
+    let mut res = String::with_capacity(text.len());
+    let mut matches = matches.peekable();
+    // 已被覆盖的字节偏移上界, 匹配按照起始位置升序产生
+    let mut covered = 0;
+    for (i, ch) in text.char_indices() {
+        while let Some(found) = matches.next_if(|found| found.start <= i) {
+            covered = covered.max(found.end);
+        }
+        res.push(if i < covered { mask } else { ch });
+    }
+    res
+}
+
+impl<S: BuildHasher> TrieTree<char, S> {
+    /// Find the words of the tree in the text<br/>
+    /// The tree is walked from every start position, so the cost is
+    /// O(n·m) for a text of n `char`s and words of at most m `char`s.
+    /// For many words over long texts, see [`TrieTree::to_aho_corasick`]
+    /// which finds the overlapping matches in one linear pass
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, Match, MatchKind, MatchOptions};
+    /// let tree = TrieTree::from_iter(["he", "hers", "she"].map(str::chars));
+    /// let words = |text, options| -> Vec<&str> {
+    ///     tree.find_all(text, options).map(|m: Match| &text[m.range()]).collect()
+    /// };
+    /// assert_eq!(words("ushers", MatchOptions::new()), ["she"]);
+    /// assert_eq!(words("ushers", MatchOptions::new().overlapping(true)), ["she", "he", "hers"]);
+    /// assert_eq!(words("hers", MatchOptions::new()), ["hers"]);
+    /// assert_eq!(words("hers", MatchOptions::new().kind(MatchKind::LeftmostShortest)), ["he"]);
+    /// ```
+    pub fn find_all<'a>(&'a self, text: &'a str, options: MatchOptions) -> FindIter<'a, S> {
+        FindIter {
+            words: Words::Tree(&self.root),
+            text,
+            options,
+            pos: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Replace the matches of the words in the text by `f`,
+    /// a match overlapping the previous replaced match is not replaced
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, MatchOptions};
+    /// let tree = TrieTree::from_iter(["cat", "dog"].map(str::chars));
+    /// let text = "cat and dog";
+    /// let res = tree.replace_all(text, MatchOptions::new(), |m| {
+    ///     text[m.range()].to_uppercase()
+    /// });
+    /// assert_eq!(res, "CAT and DOG");
+    /// ```
+    pub fn replace_all<F, R>(&self, text: &str, options: MatchOptions, f: F) -> String
+    where F: FnMut(Match) -> R,
+          R: AsRef<str>,
+    {
+        replace_matches(text, self.find_all(text, options), f)
+    }
+
+    /// Replace each `char` covered by any match with `mask`
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, MatchOptions};
+    /// let tree = TrieTree::from_iter(["坏词", "词语"].map(str::chars));
+    /// let options = MatchOptions::new().overlapping(true);
+    /// assert_eq!(tree.mask("一个坏词语", options, '*'), "一个***");
+    /// ```
+    pub fn mask(&self, text: &str, options: MatchOptions, mask: char) -> String {
+        mask_matches(text, self.find_all(text, options), mask)
+    }
+}
+
+/// A word list for [`find_all`](Self::find_all) that records
+/// the insertion order of the words, for [`MatchKind::LeftmostFirst`]
+/// ===
+/// Inserting a word again keeps its original order,
+/// removing a word and inserting it again moves it to the end.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::{WordFilter, MatchKind, MatchOptions};
+/// let filter = WordFilter::from_iter(["hers", "he", "her"]);
+/// let words = |options| -> Vec<&str> {
+///     filter.find_all("hers", options).map(|m| &"hers"[m.range()]).collect()
+/// };
+/// assert_eq!(words(MatchOptions::new().kind(MatchKind::LeftmostFirst)), ["hers"]);
+/// assert_eq!(words(MatchOptions::new().kind(MatchKind::LeftmostShortest)), ["he"]);
+/// ```
+#[derive(Clone)]
+pub struct WordFilter<S = RandomState> {
+    /// 终节点存储词的插入序号
+    arena: Arena<char, usize, S>,
+    /// 下一个插入的词的序号
+    next_order: usize,
+    count: usize,
+}
+
+impl WordFilter {
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for WordFilter {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<S> WordFilter<S> {
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            arena: Arena::with_capacity_and_hasher(1, hasher),
+            next_order: 0,
+            count: 0,
+        }
+    }
+
+    /// return words count
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl<S: BuildHasher> WordFilter<S> {
+    /// clear all words
+    pub fn clear(&mut self) {
+        self.arena.clear();
+        self.next_order = 0;
+        self.count = 0;
+    }
+
+    /// insert a word after all inserted words
+    /// - return whether the word was newly inserted
+    pub fn insert(&mut self, word: &str) -> bool
+    where S: Clone
+    {
+        let node = self.arena.get_or_insert_node(word.chars());
+        if self.arena[node].value.is_some() {
+            return false;
+        }
+        self.arena[node].value = Some(self.next_order);
+        self.next_order += 1;
+        self.count += 1;
+        true
+    }
+
+    /// Whether the word is in the filter
+    pub fn contains(&self, word: &str) -> bool {
+        self.arena.query_node(word.chars())
+            .is_some_and(|node| self.arena[node].is_stop())
+    }
+
+    /// remove a word
+    /// - return whether the word was in the filter
+    pub fn remove(&mut self, word: &str) -> bool {
+        let removed = self.arena.remove(word.chars()).is_some();
+        if removed {
+            self.count -= 1;
+        }
+        removed
+    }
+
+    /// Find the words in the text, see [`TrieTree::find_all`]
+    pub fn find_all<'a>(&'a self, text: &'a str, options: MatchOptions) -> FindIter<'a, S> {
+        FindIter {
+            words: Words::Ordered(&self.arena),
+            text,
+            options,
+            pos: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Replace the matches of the words in the text by `f`,
+    /// see [`TrieTree::replace_all`]
+    pub fn replace_all<F, R>(&self, text: &str, options: MatchOptions, f: F) -> String
+    where F: FnMut(Match) -> R,
+          R: AsRef<str>,
+    {
+        replace_matches(text, self.find_all(text, options), f)
+    }
+
+    /// Replace each `char` covered by any match with `mask`,
+    /// see [`TrieTree::mask`]
+    pub fn mask(&self, text: &str, options: MatchOptions, mask: char) -> String {
+        mask_matches(text, self.find_all(text, options), mask)
+    }
+}
+
+impl<'a> FromIterator<&'a str> for WordFilter {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut filter = Self::new();
+        filter.extend(iter);
+        filter
+    }
+}
+
+impl<'a, S> Extend<&'a str> for WordFilter<S>
+where S: BuildHasher + Clone,
+{
+    fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+        for word in iter {
+            self.insert(word);
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use core::fmt::{self, Debug};
+use alloc::{vec, vec::Vec};
+
+/// An unsigned integer that can be used as the key of [`IntBitTrie`]
+pub trait BitKey: Copy + Ord {
+    /// number of bits
+    const BITS: u32;
+
+    /// convert into the low bits of `u128`
+    fn to_u128(self) -> u128;
+
+    /// convert from the low bits of `u128`
+    fn from_u128(bits: u128) -> Self;
+}
+
+macro_rules! impl_bit_key {
+    ($($ty:ty),+ $(,)?) => {$(
+        impl BitKey for $ty {
+            const BITS: u32 = <$ty>::BITS;
+
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+
+            fn from_u128(bits: u128) -> Self {
+                bits as $ty
+            }
+        }
+    )+};
+}
+
+impl_bit_key!(u8, u16, u32, u64, u128, usize);
+
+/// 根节点的下标, 根节点不会是其他节点的子节点, 所以子节点下标为零表示没有子节点
+const ROOT: usize = 0;
+
+#[derive(Clone, Default)]
+struct CountNode {
+    childs: [usize; 2],
+    /// 子树中的值数量
+    count: usize,
+}
+
+/// A binary trie of unsigned integers keyed by their bits, from high to low
+/// ===
+/// Every node keeps the number of values in its subtree,
+/// so the order statistics and the XOR queries take `O(K::BITS)` time.
+/// Removing a value frees the nodes left without values for later insertions.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::IntBitTrie;
+/// let mut tree = IntBitTrie::<u32>::from_iter([3, 10, 5, 25, 2, 8]);
+/// assert_eq!(tree.max_xor(5), Some(25)); // 5 ^ 25 == 28
+/// assert_eq!(tree.min_xor(9), Some(8));
+/// assert_eq!(tree.count_less_than(8), 3);
+/// assert_eq!(tree.kth_smallest(0), Some(2));
+/// assert_eq!(tree.kth_smallest(5), Some(25));
+/// assert!(tree.remove(25));
+/// assert_eq!(tree.max_xor(5), Some(10));
+/// ```
+#[derive(Clone)]
+pub struct IntBitTrie<K> {
+    nodes: Vec<CountNode>,
+    /// 已被删除可以复用的节点下标
+    free: Vec<usize>,
+    _key: core::marker::PhantomData<K>,
+}
+
+impl<K: BitKey> Default for IntBitTrie<K> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![CountNode::default()],
+            free: Vec::new(),
+            _key: core::marker::PhantomData,
+        }
+    }
+}
+
+/// 获取值从高到低的第`index`位
+fn bit_of<K: BitKey>(key: K, index: u32) -> usize {
+    (key.to_u128() >> (K::BITS - 1 - index) & 1) as usize
+}
+
+impl<K: BitKey> IntBitTrie<K> {
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// return values count
+    pub fn count(&self) -> usize {
+        self.nodes[ROOT].count
+    }
+
+    /// clear all values
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[ROOT] = CountNode::default();
+        self.free.clear();
+    }
+
+    /// 分配一个节点, 优先复用已被删除的节点
+    fn alloc_node(&mut self) -> usize {
+        match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = CountNode::default();
+                index
+            },
+            None => {
+                self.nodes.push(CountNode::default());
+                self.nodes.len() - 1
+            },
+        }
+    }
+
+    /// Whether the value is in the tree
+    pub fn contains(&self, key: K) -> bool {
+        let mut node = ROOT;
+        for i in 0..K::BITS {
+            node = self.nodes[node].childs[bit_of(key, i)];
+            if node == ROOT {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// insert a value
+    /// - return whether the insertion value was successful
+    pub fn insert(&mut self, key: K) -> bool {
+        if self.contains(key) {
+            return false;
+        }
+        let mut node = ROOT;
+        self.nodes[node].count += 1;
+        for i in 0..K::BITS {
+            let bit = bit_of(key, i);
+            let mut child = self.nodes[node].childs[bit];
+            if child == ROOT {
+                child = self.alloc_node();
+                self.nodes[node].childs[bit] = child;
+            }
+            node = child;
+            self.nodes[node].count += 1;
+        }
+        true
+    }
+
+    /// remove a value, the nodes without values are put into the free list
+    /// - return whether the value was in the tree
+    pub fn remove(&mut self, key: K) -> bool {
+        if ! self.contains(key) {
+            return false;
+        }
+        let mut node = ROOT;
+        self.nodes[node].count -= 1;
+        for i in 0..K::BITS {
+            let bit = bit_of(key, i);
+            let child = self.nodes[node].childs[bit];
+            self.nodes[child].count -= 1;
+            if self.nodes[child].count == 0 {
+                // 该节点以下的节点都只包含该值, 全部释放
+                self.nodes[node].childs[bit] = ROOT;
+                let mut freed = child;
+                for i in i+1..K::BITS {
+                    self.free.push(freed);
+                    freed = self.nodes[freed].childs[bit_of(key, i)];
+                }
+                self.free.push(freed);
+                break;
+            }
+            node = child;
+        }
+        true
+    }
+
+    /// 沿着每一位尽量选择`prefer`给出的位的路径, 返回到达的值
+    fn walk(&self, mut prefer: impl FnMut(u32) -> usize) -> Option<K> {
+        if self.count() == 0 {
+            return None;
+        }
+        let mut node = ROOT;
+        let mut bits = 0u128;
+        for i in 0..K::BITS {
+            let mut bit = prefer(i);
+            if self.nodes[node].childs[bit] == ROOT {
+                bit ^= 1;
+            }
+            node = self.nodes[node].childs[bit];
+            bits = bits << 1 | bit as u128;
+        }
+        Some(K::from_u128(bits))
+    }
+
+    /// Get the value `y` in the tree that maximizes `x ^ y`
+    /// - return `None` if the tree is empty
+    pub fn max_xor(&self, x: K) -> Option<K> {
+        self.walk(|i| bit_of(x, i) ^ 1)
+    }
+
+    /// Get the value `y` in the tree that minimizes `x ^ y`
+    /// - return `None` if the tree is empty
+    pub fn min_xor(&self, x: K) -> Option<K> {
+        self.walk(|i| bit_of(x, i))
+    }
+
+    /// Get the number of values less than `x`
+    pub fn count_less_than(&self, x: K) -> usize {
+        let mut res = 0;
+        let mut node = ROOT;
+        for i in 0..K::BITS {
+            let [zero, one] = self.nodes[node].childs;
+            node = if bit_of(x, i) == 1 {
+                if zero != ROOT {
+                    res += self.nodes[zero].count;
+                }
+                one
+            } else {
+                zero
+            };
+            if node == ROOT {
+                break;
+            }
+        }
+        res
+    }
+
+    /// Get the `k`-th smallest value, `k` starts from zero
+    /// - return `None` if `k >= self.count()`
+    pub fn kth_smallest(&self, mut k: usize) -> Option<K> {
+        if k >= self.count() {
+            return None;
+        }
+        let mut node = ROOT;
+        let mut bits = 0u128;
+        for _ in 0..K::BITS {
+            let [zero, one] = self.nodes[node].childs;
+            let zero_count = if zero == ROOT { 0 } else { self.nodes[zero].count };
+            let bit = if k < zero_count {
+                node = zero;
+                0
+            } else {
+                k -= zero_count;
+                node = one;
+                1
+            };
+            bits = bits << 1 | bit;
+        }
+        Some(K::from_u128(bits))
+    }
+
+    /// get iterator of all values in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = K> + '_ {
+        // 节点, 深度, 路径上的位
+        let mut stack = vec![(ROOT, 0, 0u128)];
+        if self.count() == 0 {
+            stack.clear();
+        }
+        core::iter::from_fn(move || loop {
+            let (node, depth, bits) = stack.pop()?;
+            if depth == K::BITS {
+                return Some(K::from_u128(bits));
+            }
+            // 先压入位为1的子节点, 使位为0的子节点先被遍历
+            for bit in [1, 0] {
+                let child = self.nodes[node].childs[bit];
+                if child != ROOT {
+                    stack.push((child, depth + 1, bits << 1 | bit as u128));
+                }
+            }
+        })
+    }
+
+    /// Shrink the node arena and the free list
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        self.free.shrink_to_fit();
+    }
+}
+
+impl<K: BitKey + Debug> Debug for IntBitTrie<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: BitKey> FromIterator<K> for IntBitTrie<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<K: BitKey> Extend<K> for IntBitTrie<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
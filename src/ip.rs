@@ -0,0 +1,353 @@
+use core::{
+    fmt::{self, Debug, Display},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use crate::trie_node::bit::{mask_bits, BitNode};
+
+/// An IPv4 or IPv6 prefix, such as `10.0.0.0/8`
+///
+/// The host bits are always zero
+/// # Examples
+/// ```
+/// # use fast_trie_tree::IpPrefix;
+/// let prefix: IpPrefix = "10.1.2.3/8".parse().unwrap();
+/// assert_eq!(prefix.to_string(), "10.0.0.0/8");
+/// assert!(prefix.contains("10.255.0.1".parse().unwrap()));
+/// assert_eq!("::1".parse::<IpPrefix>().unwrap().prefix_len(), 128);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IpPrefix {
+    addr: IpAddr,
+    len: u8,
+}
+
+impl IpPrefix {
+    /// new Self, the host bits of `addr` are cleared
+    /// - return `None` if `len` is greater than the address bits
+    pub fn new(addr: IpAddr, len: u8) -> Option<Self> {
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        if len > max {
+            return None;
+        }
+        let bits = mask_bits(addr_bits(addr), len);
+        Some(Self::from_bits(addr.is_ipv6(), bits, len))
+    }
+
+    /// the network address
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// the number of prefix bits
+    pub fn prefix_len(&self) -> u8 {
+        self.len
+    }
+
+    /// Whether the address is in the prefix
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        addr.is_ipv6() == self.addr.is_ipv6()
+            && mask_bits(addr_bits(addr), self.len) == addr_bits(self.addr)
+    }
+
+    /// 左对齐的地址位
+    fn bits(&self) -> u128 {
+        addr_bits(self.addr)
+    }
+
+    fn from_bits(is_ipv6: bool, bits: u128, len: u8) -> Self {
+        let addr = if is_ipv6 {
+            IpAddr::V6(Ipv6Addr::from(bits))
+        } else {
+            IpAddr::V4(Ipv4Addr::from((bits >> 96) as u32))
+        };
+        Self { addr, len }
+    }
+}
+
+/// 左对齐的地址位
+fn addr_bits(addr: IpAddr) -> u128 {
+    match addr {
+        IpAddr::V4(addr) => (u32::from(addr) as u128) << 96,
+        IpAddr::V6(addr) => u128::from(addr),
+    }
+}
+
+impl From<IpAddr> for IpPrefix {
+    /// the prefix that contains only the address
+    fn from(addr: IpAddr) -> Self {
+        let len = if addr.is_ipv4() { 32 } else { 128 };
+        Self { addr, len }
+    }
+}
+
+impl Display for IpPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.len)
+    }
+}
+
+/// Error of parsing an [`IpPrefix`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidIpPrefix;
+
+impl Display for InvalidIpPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid IP prefix syntax")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidIpPrefix {}
+
+impl FromStr for IpPrefix {
+    type Err = InvalidIpPrefix;
+
+    /// Parse `addr/len` or `addr`, the latter is a full length prefix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, len) = match s.split_once('/') {
+            Some((addr, len)) => (addr, Some(len)),
+            None => (s, None),
+        };
+        let addr = addr.parse::<IpAddr>().map_err(|_| InvalidIpPrefix)?;
+        match len {
+            Some(len) => {
+                // 不接受`+8`等格式
+                if ! len.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(InvalidIpPrefix);
+                }
+                let len = len.parse().map_err(|_| InvalidIpPrefix)?;
+                Self::new(addr, len).ok_or(InvalidIpPrefix)
+            },
+            None => Ok(addr.into()),
+        }
+    }
+}
+
+/// A longest-prefix-match table of IPv4 and IPv6 prefixes
+/// ===
+/// Built on a binary trie keyed by the prefix bits
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::{IpPrefixTrie, IpPrefix};
+/// # use std::net::IpAddr;
+/// let prefix = |s: &str| s.parse::<IpPrefix>().unwrap();
+/// let addr = |s: &str| s.parse::<IpAddr>().unwrap();
+///
+/// let mut table = IpPrefixTrie::new();
+/// table.insert(prefix("0.0.0.0/0"), "default");
+/// table.insert(prefix("10.0.0.0/8"), "private");
+/// table.insert(prefix("10.1.0.0/16"), "office");
+///
+/// assert_eq!(table.longest_match(addr("10.1.2.3")), Some((prefix("10.1.0.0/16"), &"office")));
+/// assert_eq!(table.longest_match(addr("10.2.0.1")), Some((prefix("10.0.0.0/8"), &"private")));
+/// assert_eq!(table.longest_match(addr("8.8.8.8")).unwrap().1, &"default");
+/// assert_eq!(table.longest_match(addr("::1")), None);
+/// ```
+pub struct IpPrefixTrie<V> {
+    v4: BitNode<V>,
+    v6: BitNode<V>,
+    count: usize,
+}
+
+impl<V> Default for IpPrefixTrie<V> {
+    fn default() -> Self {
+        Self {
+            v4: BitNode::default(),
+            v6: BitNode::default(),
+            count: 0,
+        }
+    }
+}
+
+impl<V> IpPrefixTrie<V> {
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// return prefixes count
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    fn root(&self, is_ipv6: bool) -> &BitNode<V> {
+        if is_ipv6 { &self.v6 } else { &self.v4 }
+    }
+
+    fn root_mut(&mut self, is_ipv6: bool) -> &mut BitNode<V> {
+        if is_ipv6 { &mut self.v6 } else { &mut self.v4 }
+    }
+
+    /// clear all prefixes
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// insert a prefix
+    /// - return the old value if the prefix is already in the table
+    pub fn insert(&mut self, prefix: IpPrefix, value: V) -> Option<V> {
+        let node = self.root_mut(prefix.addr.is_ipv6())
+            .get_or_insert_node(prefix.bits(), prefix.len);
+        let old = node.value.replace(value);
+        if old.is_none() {
+            self.count += 1;
+        }
+        old
+    }
+
+    /// get the value of the exactly same prefix
+    pub fn get(&self, prefix: IpPrefix) -> Option<&V> {
+        self.root(prefix.addr.is_ipv6())
+            .query_node(prefix.bits(), prefix.len)?
+            .value
+            .as_ref()
+    }
+
+    /// get the mutable value of the exactly same prefix
+    pub fn get_mut(&mut self, prefix: IpPrefix) -> Option<&mut V> {
+        self.root_mut(prefix.addr.is_ipv6())
+            .query_node_mut(prefix.bits(), prefix.len)?
+            .value
+            .as_mut()
+    }
+
+    /// remove a prefix and clean up overhanging branches
+    /// - return the value of the removed prefix
+    pub fn remove(&mut self, prefix: IpPrefix) -> Option<V> {
+        let value = self.root_mut(prefix.addr.is_ipv6())
+            .remove(prefix.bits(), prefix.len)?;
+        self.count -= 1;
+        Some(value)
+    }
+
+    /// Get the longest prefix that contains the address
+    pub fn longest_match(&self, addr: IpAddr) -> Option<(IpPrefix, &V)> {
+        self.covering(addr.into()).last()
+    }
+
+    /// Get the Iterator of the prefixes that contain the prefix,
+    /// including itself, from the shortest to the longest
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{IpPrefixTrie, IpPrefix};
+    /// let prefix = |s: &str| s.parse::<IpPrefix>().unwrap();
+    /// let table = IpPrefixTrie::from_iter([
+    ///     (prefix("10.0.0.0/8"), 1),
+    ///     (prefix("10.1.0.0/16"), 2),
+    ///     (prefix("10.1.1.0/24"), 3),
+    /// ]);
+    /// let covering: Vec<_> = table.covering(prefix("10.1.0.0/20")).map(|(_, &v)| v).collect();
+    /// assert_eq!(covering, [1, 2]);
+    /// ```
+    pub fn covering(&self, prefix: IpPrefix) -> impl Iterator<Item = (IpPrefix, &V)> {
+        let (is_ipv6, bits) = (prefix.addr.is_ipv6(), prefix.bits());
+        self.root(is_ipv6)
+            .path_values(bits, prefix.len)
+            .map(move |(len, value)| {
+                (IpPrefix::from_bits(is_ipv6, mask_bits(bits, len), len), value)
+            })
+    }
+
+    /// Get the Iterator of the prefixes contained in the prefix,
+    /// including itself, in address order and shorter prefixes first
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{IpPrefixTrie, IpPrefix};
+    /// let prefix = |s: &str| s.parse::<IpPrefix>().unwrap();
+    /// let table = IpPrefixTrie::from_iter([
+    ///     (prefix("10.0.0.0/8"), 1),
+    ///     (prefix("10.1.1.0/24"), 3),
+    ///     (prefix("10.1.0.0/16"), 2),
+    ///     (prefix("11.0.0.0/8"), 4),
+    /// ]);
+    /// let covered: Vec<_> = table.covered(prefix("10.1.0.0/16")).map(|(_, &v)| v).collect();
+    /// assert_eq!(covered, [2, 3]);
+    /// ```
+    pub fn covered(&self, prefix: IpPrefix) -> impl Iterator<Item = (IpPrefix, &V)> {
+        let (is_ipv6, bits) = (prefix.addr.is_ipv6(), prefix.bits());
+        self.root(is_ipv6)
+            .query_node(bits, prefix.len)
+            .map(|node| node.iter(bits, prefix.len))
+            .into_iter()
+            .flatten()
+            .map(move |(bits, len, value)| (IpPrefix::from_bits(is_ipv6, bits, len), value))
+    }
+
+    /// get iterator of all prefixes, the IPv4 prefixes first,
+    /// in address order and shorter prefixes first
+    pub fn iter(&self) -> impl Iterator<Item = (IpPrefix, &V)> {
+        let v4 = self.v4.iter(0, 0)
+            .map(|(bits, len, value)| (IpPrefix::from_bits(false, bits, len), value));
+        let v6 = self.v6.iter(0, 0)
+            .map(|(bits, len, value)| (IpPrefix::from_bits(true, bits, len), value));
+        v4.chain(v6)
+    }
+
+    /// Aggregate the prefixes without changing the longest match result
+    /// of any address
+    /// - remove the prefixes that have the same value as their nearest
+    ///   covering prefix
+    /// - merge two adjacent prefixes that have the same value into their
+    ///   parent prefix, if the parent prefix is not in the table,
+    ///   the more specific prefixes under them are kept
+    ///
+    /// return the number of reduced prefixes
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{IpPrefixTrie, IpPrefix};
+    /// let prefix = |s: &str| s.parse::<IpPrefix>().unwrap();
+    /// let mut table = IpPrefixTrie::from_iter([
+    ///     (prefix("10.0.0.0/24"), "a"),
+    ///     (prefix("10.0.1.0/24"), "a"),
+    ///     (prefix("10.0.0.0/16"), "b"),
+    ///     (prefix("10.0.2.0/24"), "b"),
+    /// ]);
+    /// assert_eq!(table.aggregate(), 2);
+    /// let prefixes: Vec<_> = table.iter().map(|(p, &v)| (p.to_string(), v)).collect();
+    /// assert_eq!(prefixes, [("10.0.0.0/16".into(), "b"), ("10.0.0.0/23".into(), "a")]);
+    /// ```
+    pub fn aggregate(&mut self) -> usize
+    where V: PartialEq
+    {
+        let removed = self.v4.aggregate() + self.v6.aggregate();
+        self.count -= removed;
+        removed
+    }
+}
+
+impl<V: Debug> Debug for IpPrefixTrie<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.iter().map(|(prefix, value)| (DisplayAsDebug(prefix), value)))
+            .finish()
+    }
+}
+
+/// 使用`Display`实现`Debug`
+struct DisplayAsDebug<T>(T);
+
+impl<T: Display> Debug for DisplayAsDebug<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl<V> FromIterator<(IpPrefix, V)> for IpPrefixTrie<V> {
+    fn from_iter<I: IntoIterator<Item = (IpPrefix, V)>>(iter: I) -> Self {
+        let mut table = Self::new();
+        table.extend(iter);
+        table
+    }
+}
+
+impl<V> Extend<(IpPrefix, V)> for IpPrefixTrie<V> {
+    fn extend<I: IntoIterator<Item = (IpPrefix, V)>>(&mut self, iter: I) {
+        for (prefix, value) in iter {
+            self.insert(prefix, value);
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use core::{
+    array,
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash},
+    iter::Copied,
+    mem::size_of,
+    slice,
+    str::Chars,
+};
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::{
+    ffi::OsString,
+    path::{Component, Components, Path, PathBuf},
+};
+
+use crate::{collections::RandomState, Iter, TrieTree};
+
+/// A key that can be split into the elements of a [`TrieTree`],
+/// and be rebuilt from them
+///
+/// Implemented for `str`, `[u8]`, `Path` (with `std`) and the integers
+/// (big-endian bytes)
+/// # Examples
+/// ```
+/// # use fast_trie_tree::TrieKey;
+/// assert_eq!("ab".elems().collect::<Vec<_>>(), ['a', 'b']);
+/// assert_eq!(<str as TrieKey>::from_elems(['a', 'b'].iter()), "ab");
+/// assert_eq!(0x0102u16.elems().collect::<Vec<_>>(), [1, 2]);
+/// ```
+pub trait TrieKey {
+    /// element stored in each node
+    type Elem: Hash + Eq;
+    /// owned key rebuilt from the elements
+    type Owned;
+    /// iterator over the elements of a key
+    type Elems<'a>: Iterator<Item = Self::Elem>
+    where Self: 'a;
+
+    /// split the key into elements
+    fn elems(&self) -> Self::Elems<'_>;
+
+    /// rebuild an owned key from the elements
+    fn from_elems<'a>(elems: impl Iterator<Item = &'a Self::Elem>) -> Self::Owned
+    where Self::Elem: 'a;
+}
+
+impl TrieKey for str {
+    type Elem = char;
+    type Owned = String;
+    type Elems<'a> = Chars<'a>;
+
+    fn elems(&self) -> Self::Elems<'_> {
+        self.chars()
+    }
+
+    fn from_elems<'a>(elems: impl Iterator<Item = &'a char>) -> String {
+        elems.collect()
+    }
+}
+
+impl TrieKey for [u8] {
+    type Elem = u8;
+    type Owned = Vec<u8>;
+    type Elems<'a> = Copied<slice::Iter<'a, u8>>;
+
+    fn elems(&self) -> Self::Elems<'_> {
+        self.iter().copied()
+    }
+
+    fn from_elems<'a>(elems: impl Iterator<Item = &'a u8>) -> Vec<u8> {
+        elems.copied().collect()
+    }
+}
+
+/// Split by [`Path::components`], so `a//b` and `a/b` are the same key
+#[cfg(feature = "std")]
+impl TrieKey for Path {
+    type Elem = OsString;
+    type Owned = PathBuf;
+    type Elems<'a> = core::iter::Map<Components<'a>, fn(Component<'_>) -> OsString>;
+
+    fn elems(&self) -> Self::Elems<'_> {
+        self.components().map(|component| component.as_os_str().to_owned())
+    }
+
+    fn from_elems<'a>(elems: impl Iterator<Item = &'a OsString>) -> PathBuf {
+        elems.collect()
+    }
+}
+
+macro_rules! impl_int_key {
+    ($($ty:ty),+ $(,)?) => {$(
+        impl TrieKey for $ty {
+            type Elem = u8;
+            type Owned = $ty;
+            type Elems<'a> = array::IntoIter<u8, { size_of::<$ty>() }>;
+
+            fn elems(&self) -> Self::Elems<'_> {
+                self.to_be_bytes().into_iter()
+            }
+
+            fn from_elems<'a>(elems: impl Iterator<Item = &'a u8>) -> $ty {
+                let mut bytes = [0; size_of::<$ty>()];
+                for (byte, &elem) in bytes.iter_mut().zip(elems) {
+                    *byte = elem;
+                }
+                <$ty>::from_be_bytes(bytes)
+            }
+        }
+    )+};
+}
+
+impl_int_key!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// A [`TrieTree`] whose values are [`TrieKey`]s
+/// ===
+/// Accepts the keys directly instead of element iterators,
+/// and iterates over the rebuilt owned keys
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::KeyTrie;
+/// let mut tree = KeyTrie::<str>::new();
+/// assert!(tree.insert("abc"));
+/// assert!(tree.insert("abd"));
+/// assert!(tree.query("abc"));
+/// assert!(! tree.query("ab"));
+/// let mut keys: Vec<String> = tree.query_iter("ab").unwrap().collect();
+/// keys.sort();
+/// assert_eq!(keys, ["abc", "abd"]);
+///
+/// let nums = KeyTrie::<u32>::from_iter([&1, &0x100]);
+/// assert!(nums.query(&0x100));
+/// assert_eq!(nums.iter().count(), 2);
+/// ```
+pub struct KeyTrie<K, S = RandomState>
+where K: TrieKey + ?Sized,
+{
+    tree: TrieTree<K::Elem, S>,
+}
+
+impl<K> KeyTrie<K>
+where K: TrieKey + ?Sized,
+{
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<K> Default for KeyTrie<K>
+where K: TrieKey + ?Sized,
+{
+    fn default() -> Self {
+        Self { tree: TrieTree::new() }
+    }
+}
+
+impl<K, S> KeyTrie<K, S>
+where K: TrieKey + ?Sized,
+{
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self { tree: TrieTree::with_hasher(hasher) }
+    }
+
+    /// return keys count
+    pub fn count(&self) -> usize {
+        self.tree.count()
+    }
+
+    /// get the inner tree of elements
+    pub fn as_tree(&self) -> &TrieTree<K::Elem, S> {
+        &self.tree
+    }
+
+    /// into the inner tree of elements
+    pub fn into_tree(self) -> TrieTree<K::Elem, S> {
+        self.tree
+    }
+}
+
+impl<K, S> KeyTrie<K, S>
+where K: TrieKey + ?Sized,
+      S: BuildHasher,
+{
+    /// get iterator of the owned keys
+    pub fn iter(&self) -> KeyIter<'_, K, S> {
+        KeyIter { iter: self.tree.iter(), prefix: Vec::new() }
+    }
+
+    /// clear all keys
+    pub fn clear(&mut self)
+    where S: Clone
+    {
+        self.tree.clear();
+    }
+
+    /// insert a key
+    /// - return whether the insertion key was successful
+    pub fn insert(&mut self, key: &K) -> bool
+    where S: Clone
+    {
+        self.tree.insert(key.elems())
+    }
+
+    /// Whether the key is in the tree
+    pub fn query(&self, key: &K) -> bool {
+        self.tree.query(key.elems())
+    }
+
+    /// Whether the key is a prefix of the keys in the tree
+    /// - see [`TrieTree::query_nostop`]
+    pub fn query_nostop(&self, key: &K) -> Option<bool> {
+        self.tree.query_nostop(key.elems())
+    }
+
+    /// Get the Iterator of the keys starting with the prefix,
+    /// the yielded keys include the prefix<br/>
+    /// If there is no matching key, return `None`
+    pub fn query_iter(&self, prefix: &K) -> Option<KeyIter<'_, K, S>> {
+        let prefix: Vec<_> = prefix.elems().collect();
+        let iter = self.tree.query_iter(&prefix)?;
+        Some(KeyIter { iter, prefix })
+    }
+
+    /// remove a key and clean up overhanging branches
+    pub fn remove(&mut self, key: &K) -> bool {
+        self.tree.remove(key.elems())
+    }
+
+    /// Shrink each `HashMap` in the tree
+    pub fn shrink_to_fit(&mut self) {
+        self.tree.shrink_to_fit();
+    }
+}
+
+impl<K, S> From<TrieTree<K::Elem, S>> for KeyTrie<K, S>
+where K: TrieKey + ?Sized,
+{
+    fn from(tree: TrieTree<K::Elem, S>) -> Self {
+        Self { tree }
+    }
+}
+
+impl<K, S> Clone for KeyTrie<K, S>
+where K: TrieKey + ?Sized,
+      K::Elem: Clone,
+      S: BuildHasher + Clone,
+{
+    fn clone(&self) -> Self {
+        Self { tree: self.tree.clone() }
+    }
+}
+
+impl<K, S> Debug for KeyTrie<K, S>
+where K: TrieKey + ?Sized,
+      K::Elem: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tree.fmt(f)
+    }
+}
+
+impl<K, S> PartialEq for KeyTrie<K, S>
+where K: TrieKey + ?Sized,
+      S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.tree == other.tree
+    }
+}
+
+impl<K, S> Eq for KeyTrie<K, S>
+where K: TrieKey + ?Sized,
+      S: BuildHasher,
+{
+}
+
+impl<'k, K> FromIterator<&'k K> for KeyTrie<K>
+where K: TrieKey + ?Sized + 'k,
+{
+    fn from_iter<I: IntoIterator<Item = &'k K>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<'k, K, S> Extend<&'k K> for KeyTrie<K, S>
+where K: TrieKey + ?Sized + 'k,
+      S: BuildHasher + Clone,
+{
+    fn extend<I: IntoIterator<Item = &'k K>>(&mut self, iter: I) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<'a, K, S> IntoIterator for &'a KeyTrie<K, S>
+where K: TrieKey + ?Sized,
+      S: BuildHasher,
+{
+    type Item = K::Owned;
+    type IntoIter = KeyIter<'a, K, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator used to iterate over the owned keys of `KeyTrie`
+pub struct KeyIter<'a, K, S = RandomState>
+where K: TrieKey + ?Sized,
+{
+    iter: Iter<'a, K::Elem, S>,
+    /// 查询的前缀, 拼接在每个值之前
+    prefix: Vec<K::Elem>,
+}
+
+impl<K, S> Default for KeyIter<'_, K, S>
+where K: TrieKey + ?Sized,
+{
+    /// an iterator that yields nothing
+    fn default() -> Self {
+        Self { iter: Iter::default(), prefix: Vec::new() }
+    }
+}
+
+impl<K, S> Iterator for KeyIter<'_, K, S>
+where K: TrieKey + ?Sized,
+{
+    type Item = K::Owned;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prefix = &self.prefix;
+        self.iter.next_op(|datas| {
+            K::from_elems(prefix.iter().chain(datas.iter().copied()))
+        })
+    }
+}
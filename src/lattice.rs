@@ -0,0 +1,309 @@
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+    ops::Range,
+};
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    arena::{Arena, ROOT},
+    collections::RandomState,
+    TrieTree,
+};
+
+/// An edge of [`Lattice`], a dictionary word or an unknown `char`
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatticeEdge<'a> {
+    /// the byte range in the text
+    pub span: Range<usize>,
+    /// the text of the edge
+    pub word: &'a str,
+    /// whether it is a dictionary word,
+    /// otherwise it is a single `char` without any word starting at it
+    pub is_word: bool,
+    /// the weight stored in the dictionary, see [`WeightedTrie`]
+    pub weight: Option<f64>,
+}
+
+/// 字典匹配的边, 终点为字符下标
+#[derive(Debug, Clone, Copy)]
+struct RawEdge {
+    end: usize,
+    is_word: bool,
+    weight: Option<f64>,
+}
+
+/// The word lattice (DAG) of a text, containing every dictionary match
+/// at every position
+/// ===
+/// The nodes are the `char` boundaries of the text,
+/// each edge goes from the start to the end of a match.
+/// A position where no word starts has an edge of its single `char`,
+/// so there is always a path from the start to the end of the text.
+///
+/// See [`TrieTree::build_lattice`] and [`WeightedTrie::build_lattice`]
+#[derive(Clone)]
+pub struct Lattice<'a> {
+    text: &'a str,
+    /// 每个字符的字节偏移, 末尾附加文本长度
+    offsets: Vec<usize>,
+    /// 由每个字符下标出发的边, 按照终点升序
+    edges: Vec<Vec<RawEdge>>,
+}
+
+impl<'a> Lattice<'a> {
+    /// 由每个位置出发的字典匹配构建, `walk`产生由字符下标开始的所有词的终点与权重
+    fn build<F>(text: &'a str, mut walk: F) -> Self
+    where F: FnMut(&[char], &mut Vec<RawEdge>),
+    {
+        let mut offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        offsets.push(text.len());
+        let chars: Vec<char> = text.chars().collect();
+        let edges = (0..chars.len()).map(|start| {
+            let mut edges = Vec::new();
+            walk(&chars[start..], &mut edges);
+            for edge in &mut edges {
+                edge.end += start;
+            }
+            if edges.is_empty() {
+                edges.push(RawEdge { end: start + 1, is_word: false, weight: None });
+            }
+            edges
+        }).collect();
+        Self { text, offsets, edges }
+    }
+
+    /// get the text
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    fn edge(&self, start: usize, edge: &RawEdge) -> LatticeEdge<'a> {
+        let span = self.offsets[start]..self.offsets[edge.end];
+        LatticeEdge {
+            word: &self.text[span.clone()],
+            span,
+            is_word: edge.is_word,
+            weight: edge.weight,
+        }
+    }
+
+    /// get iterator of all edges, ordered by start then end
+    pub fn edges(&self) -> impl Iterator<Item = LatticeEdge<'a>> + '_ {
+        self.edges.iter()
+            .enumerate()
+            .flat_map(move |(start, edges)| {
+                edges.iter().map(move |edge| self.edge(start, edge))
+            })
+    }
+
+    /// Get the path with the maximum sum of the edge weights,
+    /// such as the log probabilities of the words, by dynamic programming<br/>
+    /// If multiple edges from a position reach the same score, the longer is taken
+    /// - return the byte ranges of the path, in text order
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::WeightedTrie;
+    /// let dict = WeightedTrie::from_iter([
+    ///     ("研究", -6.0), ("研究生", -9.0), ("生命", -7.0), ("命", -8.0),
+    ///     ("的", -3.0), ("起源", -8.0),
+    /// ]);
+    /// let text = "研究生命的起源";
+    /// let lattice = dict.build_lattice(text);
+    /// let path = lattice.best_path(|edge| edge.weight.unwrap_or(-20.0));
+    /// let words: Vec<&str> = path.into_iter().map(|span| &text[span]).collect();
+    /// assert_eq!(words, ["研究", "生命", "的", "起源"]);
+    /// ```
+    pub fn best_path<F>(&self, mut weights: F) -> Vec<Range<usize>>
+    where F: FnMut(&LatticeEdge<'a>) -> f64,
+    {
+        let len = self.edges.len();
+        // 由每个位置到末尾的最大分数以及选择的边终点, 逆序计算
+        let mut routes = vec![(0.0, len); len + 1];
+        for start in (0..len).rev() {
+            let mut best = (f64::NEG_INFINITY, start + 1);
+            for edge in &self.edges[start] {
+                let score = weights(&self.edge(start, edge)) + routes[edge.end].0;
+                if score >= best.0 {
+                    best = (score, edge.end);
+                }
+            }
+            routes[start] = best;
+        }
+        let mut path = Vec::new();
+        let mut start = 0;
+        while start < len {
+            let end = routes[start].1;
+            path.push(self.offsets[start]..self.offsets[end]);
+            start = end;
+        }
+        path
+    }
+}
+
+impl Debug for Lattice<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.edges()).finish()
+    }
+}
+
+impl<S: BuildHasher> TrieTree<char, S> {
+    /// Build the word lattice of the text, the edges have no weights
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// let tree = TrieTree::from_iter(["ab", "abc", "bc", "c"].map(str::chars));
+    /// let lattice = tree.build_lattice("abcd");
+    /// let words: Vec<&str> = lattice.edges().map(|edge| edge.word).collect();
+    /// assert_eq!(words, ["ab", "abc", "bc", "c", "d"]);
+    /// ```
+    pub fn build_lattice<'a>(&self, text: &'a str) -> Lattice<'a> {
+        Lattice::build(text, |chars, edges| {
+            let mut node = &self.root;
+            for (end, ch) in (1..).zip(chars) {
+                let Some(child) = node.get_child(ch) else { break };
+                node = child;
+                if node.is_stop() {
+                    edges.push(RawEdge { end, is_word: true, weight: None });
+                }
+            }
+        })
+    }
+}
+
+/// A dictionary of words with weights stored at their stop nodes
+/// ===
+/// The nodes of removed words are reused by later insertions,
+/// so [`node_count`](WeightedTrie::node_count) only counts the nodes in use.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::WeightedTrie;
+/// let mut dict = WeightedTrie::new();
+/// assert_eq!(dict.insert("trie", 2.5), None);
+/// assert_eq!(dict.insert("trie", 3.0), Some(2.5));
+/// assert_eq!(dict.get("trie"), Some(3.0));
+/// assert_eq!(dict.get("tri"), None);
+/// assert_eq!(dict.count(), 1);
+/// ```
+#[derive(Clone)]
+pub struct WeightedTrie<S = RandomState> {
+    arena: Arena<char, f64, S>,
+    count: usize,
+}
+
+impl WeightedTrie {
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for WeightedTrie {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<S> WeightedTrie<S> {
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            arena: Arena::with_capacity_and_hasher(1, hasher),
+            count: 0,
+        }
+    }
+
+    /// return words count
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// return the number of nodes in use, including the root
+    pub fn node_count(&self) -> usize {
+        self.arena.node_count()
+    }
+}
+
+impl<S: BuildHasher> WeightedTrie<S> {
+    /// clear all words
+    pub fn clear(&mut self) {
+        self.arena.clear();
+        self.count = 0;
+    }
+
+    /// insert a word with its weight
+    /// - return the old weight of the word
+    pub fn insert(&mut self, word: &str, weight: f64) -> Option<f64>
+    where S: Clone
+    {
+        let node = self.arena.get_or_insert_node(word.chars());
+        let old = self.arena[node].value.replace(weight);
+        if old.is_none() {
+            self.count += 1;
+        }
+        old
+    }
+
+    /// Get the weight of the word
+    pub fn get(&self, word: &str) -> Option<f64> {
+        self.arena[self.arena.query_node(word.chars())?].value
+    }
+
+    /// Whether the word is in the dictionary
+    pub fn contains(&self, word: &str) -> bool {
+        self.get(word).is_some()
+    }
+
+    /// remove a word and clean up overhanging branches,
+    /// the removed nodes are reused by later insertions
+    /// - return the weight of the word
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::WeightedTrie;
+    /// let mut dict = WeightedTrie::from_iter([("ab", 1.0), ("abcd", 2.0)]);
+    /// assert_eq!(dict.node_count(), 5);
+    /// assert_eq!(dict.remove("abcd"), Some(2.0));
+    /// assert_eq!(dict.remove("abcd"), None);
+    /// assert_eq!(dict.node_count(), 3);
+    /// ```
+    pub fn remove(&mut self, word: &str) -> Option<f64> {
+        let old = self.arena.remove(word.chars())?;
+        self.count -= 1;
+        Some(old)
+    }
+
+    /// Build the word lattice of the text, the edges of the words
+    /// carry their weights
+    pub fn build_lattice<'a>(&self, text: &'a str) -> Lattice<'a> {
+        Lattice::build(text, |chars, edges| {
+            let mut node = ROOT;
+            for (end, ch) in (1..).zip(chars) {
+                let Some(&child) = self.arena[node].childs.get(ch) else { break };
+                node = child;
+                if let Some(weight) = self.arena[node].value {
+                    edges.push(RawEdge { end, is_word: true, weight: Some(weight) });
+                }
+            }
+        })
+    }
+}
+
+impl<'a> FromIterator<(&'a str, f64)> for WeightedTrie {
+    fn from_iter<I: IntoIterator<Item = (&'a str, f64)>>(iter: I) -> Self {
+        let mut dict = Self::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+impl<'a, S> Extend<(&'a str, f64)> for WeightedTrie<S>
+where S: BuildHasher + Clone,
+{
+    fn extend<I: IntoIterator<Item = (&'a str, f64)>>(&mut self, iter: I) {
+        for (word, weight) in iter {
+            self.insert(word, weight);
+        }
+    }
+}
@@ -1,14 +1,72 @@
 #![doc = include_str!("../README.md")] // use readme
+#![cfg_attr(not(feature = "std"), no_std)]
 
-use std::{
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("either feature `std` or `alloc` must be enabled");
+
+extern crate alloc;
+
+use core::{
     borrow::Borrow,
-    hash::Hash, fmt::Debug,
+    hash::{BuildHasher, Hash}, fmt::Debug,
 };
+use alloc::vec::Vec;
 
+mod collections;
 mod trie_node;
-
+mod arena;
+mod stats;
+mod dot;
+mod display;
+mod parse;
+mod cmp;
+mod key;
+mod string;
+mod router;
+mod ip;
+mod int_bit;
+mod suffix;
+mod bi;
+mod norm;
+mod segment;
+mod lattice;
+mod filter;
+mod aho;
+#[cfg(feature = "std")]
+mod path;
+#[cfg(feature = "std")]
+mod concurrent;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "rayon")]
+mod par;
+
+use collections::RandomState;
 use trie_node::TrieNode;
 pub use trie_node::iter::Iter;
+pub use arena::{ArenaTrieTree, ArenaIter};
+pub use stats::TrieStats;
+pub use dot::DotOptions;
+pub use display::{TrieDisplay, DisplayStyle};
+pub use parse::{ParseError, ParseErrorKind};
+pub use key::{TrieKey, KeyTrie, KeyIter};
+pub use string::{StringTrie, StringIter};
+pub use router::{Router, RouteMatch, RouteError};
+pub use ip::{IpPrefix, IpPrefixTrie, InvalidIpPrefix};
+pub use int_bit::{BitKey, IntBitTrie};
+pub use suffix::SuffixIndex;
+pub use bi::BiTrie;
+pub use norm::{Normalizer, CaseFold, StripAccents, NormalizingTrie};
+pub use segment::SegmentMode;
+pub use lattice::{Lattice, LatticeEdge, WeightedTrie};
+pub use filter::{Match, MatchKind, MatchOptions, FindIter, WordFilter};
+pub use aho::{AhoCorasick, AcIter};
+#[cfg(feature = "std")]
+pub use concurrent::ConcurrentTrie;
+#[cfg(feature = "std")]
+pub use path::PathTrie;
+#[cfg(feature = "std")]
+pub use stream::{StreamScanner, StreamMatch};
 
 #[cfg(test)]
 mod tests;
@@ -20,16 +78,20 @@ mod tests;
 /// * Traversal values
 /// * Prefix lookup values
 /// * Debug Struct
-pub struct TrieTree<T>
+///
+/// `S` is the hasher builder of each node's `HashMap`,
+/// every node created by the tree uses a clone of the root's hasher builder
+pub struct TrieTree<T, S = RandomState>
 {
-    root: TrieNode<T>,
+    root: TrieNode<T, S>,
     count: usize,
 }
 
-impl<T: Hash + Eq> Eq for TrieTree<T> {}
+impl<T: Hash + Eq, S: BuildHasher> Eq for TrieTree<T, S> {}
 
-impl<T> PartialEq for TrieTree<T>
-where T: Hash + Eq
+impl<T, S> PartialEq for TrieTree<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
 {
     fn eq(&self, other: &Self) -> bool {
         self.count() == other.count()
@@ -56,8 +118,9 @@ where T: Hash + Eq,
 }
 
 
-impl<T, I> Extend<I> for TrieTree<T>
+impl<T, S, I> Extend<I> for TrieTree<T, S>
 where T: Hash + Eq,
+      S: BuildHasher + Clone,
       I: IntoIterator<Item = T>
 {
     /// # Examples
@@ -77,13 +140,13 @@ where T: Hash + Eq,
 }
 
 
-impl<T> AsRef<Self> for TrieTree<T> {
+impl<T, S> AsRef<Self> for TrieTree<T, S> {
     fn as_ref(&self) -> &Self {
         self
     }
 }
 
-impl<T> TrieTree<T> {
+impl<T, S> TrieTree<T, S> {
     /// return values count
     /// # Examples
     /// ```
@@ -109,8 +172,9 @@ impl<T> TrieTree<T> {
 
 }
 
-impl<T> Clone for TrieTree<T>
-where T: Clone
+impl<T, S> Clone for TrieTree<T, S>
+where T: Hash + Eq + Clone,
+      S: BuildHasher + Clone,
 {
     fn clone(&self) -> Self {
         Self {
@@ -120,10 +184,10 @@ where T: Clone
     }
 }
 
-impl<T: Debug> Debug for TrieTree<T>
+impl<T: Debug, S> Debug for TrieTree<T, S>
 where T: Hash + Eq
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.debug_struct("TrieTree")
             .field("count", &self.count)
             .field("root", &self.root)
@@ -135,19 +199,17 @@ impl<T> Default for TrieTree<T>
 where T: Hash + Eq,
 {
     fn default() -> Self {
-        Self {
-            root: TrieNode::default(),
-            count: 0,
-        }
+        Self::with_hasher(RandomState::default())
     }
 }
 
-impl<T> From<TrieNode<T>> for TrieTree<T>
+impl<T, S> From<TrieNode<T, S>> for TrieTree<T, S>
 where T: Hash + Eq,
+      S: BuildHasher,
 {
-    fn from(mut root: TrieNode<T>) -> Self {
+    fn from(mut root: TrieNode<T, S>) -> Self {
         let mut count = 0;
-        root.map_nodes_last_root(&mut |node: &mut TrieNode<T>| {
+        root.map_nodes_last_root(&mut |node: &mut TrieNode<T, S>| {
             if node.is_stop() {
                 count += 1;
             }
@@ -159,11 +221,12 @@ where T: Hash + Eq,
     }
 }
 
-impl<'a, T> IntoIterator for &'a TrieTree<T>
-where T: Hash + Eq
+impl<'a, T, S> IntoIterator for &'a TrieTree<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
 {
     type Item = Vec<&'a T>;
-    type IntoIter = Iter<'a, T>;
+    type IntoIter = Iter<'a, T, S>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
@@ -178,7 +241,33 @@ where T: Hash + Eq
     pub fn new() -> Self {
         Self::default()
     }
+}
 
+impl<T, S> TrieTree<T, S>
+where T: Hash + Eq
+{
+    /// new Self, use the given hasher builder for every node
+    /// - like `HashMap`, `new`, `default` and `from_iter` only use `RandomState`
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+    /// let mut tree = TrieTree::with_hasher(BuildHasherDefault::<DefaultHasher>::default());
+    /// assert!(tree.insert("abc".chars()));
+    /// assert!(tree.query("abc".chars()));
+    /// ```
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            root: TrieNode::with_hasher(hasher),
+            count: 0,
+        }
+    }
+}
+
+impl<T, S> TrieTree<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
+{
     /// clear all values<br/>
     /// It will directly discard the old root and replace them with new ones
     /// # Examples
@@ -194,16 +283,19 @@ where T: Hash + Eq
     /// assert!(! tree.query("abc".chars()));
     /// assert!(tree.insert("abc".chars()));
     /// ```
-    pub fn clear(&mut self) {
+    pub fn clear(&mut self)
+    where S: Clone
+    {
+        let hasher = self.root.childs().hasher().clone();
         unsafe {
-            self.set_root(TrieNode::default());
+            self.set_root(TrieNode::with_hasher(hasher));
             self.set_count(0);
         }
     }
 
     /// set root value
     /// * **Please do not use it! Unless you know what you're doing**
-    unsafe fn set_root(&mut self, root: TrieNode<T>) {
+    unsafe fn set_root(&mut self, root: TrieNode<T, S>) {
         self.root = root
     }
 
@@ -221,7 +313,9 @@ where T: Hash + Eq
     /// # assert!(! tree.insert("abc".chars()));
     /// # assert_eq!(tree.count(), 1);
     /// ```
-    pub fn insert(&mut self, iter: impl IntoIterator<Item = T>) -> bool {
+    pub fn insert(&mut self, iter: impl IntoIterator<Item = T>) -> bool
+    where S: Clone
+    {
         let res = self.root.insert(iter.into_iter());
         if res {
             unsafe { self.set_count(self.count() + 1) }
@@ -282,6 +376,23 @@ where T: Hash + Eq
         self.root.query_nostop(iter.into_iter())
     }
 
+    /// Get the length of the longest values in the tree
+    /// that is a prefix of the query values<br/>
+    /// If there is no such values, return `None`
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// let tree = TrieTree::from_iter(["a".chars(), "abc".chars(), "abcde".chars()]);
+    /// assert_eq!(tree.longest_prefix("abcd".chars()), Some(3));
+    /// assert_eq!(tree.longest_prefix("ab".chars()),   Some(1));
+    /// assert_eq!(tree.longest_prefix("b".chars()),    None);
+    /// ```
+    pub fn longest_prefix<Q>(&self, iter: impl IntoIterator<Item = Q>) -> Option<usize>
+    where Q: Borrow<T>
+    {
+        self.root.longest_prefix(iter.into_iter())
+    }
+
     /// Get the Iterator matching the prefix<br/>
     /// If there is no matching value, return `None`
     /// # Examples
@@ -295,7 +406,7 @@ where T: Hash + Eq
     /// res.sort();
     /// assert_eq!(res, vec![vec![&'b', &'c'], vec![&'c', &'e']]);
     /// ```
-    pub fn query_iter<Q>(&self, iter: impl IntoIterator<Item = Q>) -> Option<Iter<T>>
+    pub fn query_iter<Q>(&self, iter: impl IntoIterator<Item = Q>) -> Option<Iter<'_, T, S>>
     where Q: Borrow<T>
     {
         self.root.query_iter(iter.into_iter())
@@ -352,7 +463,7 @@ where T: Hash + Eq
     /// assert_eq!(items[0], [&'a', &'b', &'c']);
     /// assert_eq!(items[1], [&'b', &'c', &'d']);
     /// ```
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T, S> {
         self.root.iter()
     }
 
@@ -0,0 +1,293 @@
+use core::{
+    fmt::{self, Debug},
+    hash::{BuildHasher, Hash},
+};
+use alloc::vec::Vec;
+
+use crate::{
+    arena::{Arena, ROOT},
+    collections::RandomState,
+};
+
+/// Normalize the elements of the keys of [`NormalizingTrie`]
+///
+/// A closure `Fn(&T) -> Option<T>` is a one-to-one mapping normalizer,
+/// returning `None` drops the element.
+/// A tuple `(A, B)` applies `A`, then applies `B` to its output.
+pub trait Normalizer<T> {
+    /// Push the normalized elements of the element into `out`,
+    /// an element can be normalized into zero or more elements
+    fn normalize(&self, elem: T, out: &mut Vec<T>);
+
+    /// Normalize all elements of the key
+    fn normalize_all(&self, key: impl IntoIterator<Item = T>) -> Vec<T>
+    where Self: Sized
+    {
+        let mut out = Vec::new();
+        for elem in key {
+            self.normalize(elem, &mut out);
+        }
+        out
+    }
+}
+
+impl<T, F> Normalizer<T> for F
+where F: Fn(&T) -> Option<T>,
+{
+    fn normalize(&self, elem: T, out: &mut Vec<T>) {
+        out.extend(self(&elem));
+    }
+}
+
+impl<T, A, B> Normalizer<T> for (A, B)
+where A: Normalizer<T>,
+      B: Normalizer<T>,
+{
+    fn normalize(&self, elem: T, out: &mut Vec<T>) {
+        let mut buf = Vec::new();
+        self.0.normalize(elem, &mut buf);
+        for elem in buf {
+            self.1.normalize(elem, out);
+        }
+    }
+}
+
+/// Unicode case folding by [`char::to_lowercase`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CaseFold;
+
+impl Normalizer<char> for CaseFold {
+    fn normalize(&self, elem: char, out: &mut Vec<char>) {
+        out.extend(elem.to_lowercase());
+    }
+}
+
+/// Strip the accents of `char`s
+///
+/// The combining diacritical marks (`U+0300..=U+036F`) are dropped,
+/// the precomposed letters of Latin-1 Supplement and Latin Extended-A
+/// are mapped to their base letters, such as `é` to `e`.
+/// Letters without a canonical decomposition, such as `ø` and `ł`, are kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct StripAccents;
+
+/// `U+00C0..=U+017F`的基本字母, `_`表示保持不变
+const LATIN_BASES: &[u8; 192] = b"\
+    AAAAAA_CEEEEIIII_NOOOOO__UUUUY__aaaaaa_ceeeeiiii_nooooo__uuuuy_y\
+    AaAaAaCcCcCcCcDd__EeEeEeEeEeGgGgGgGgHh__IiIiIiIiI___JjKk_LlLlLl_\
+    ___NnNnNn___OoOoOo__RrRrRrSsSsSsSsTtTt__UuUuUuUuUuUuWwYyYZzZzZz_";
+
+impl Normalizer<char> for StripAccents {
+    fn normalize(&self, elem: char, out: &mut Vec<char>) {
+        match elem {
+            '\u{0300}'..='\u{036F}' => (),
+            '\u{00C0}'..='\u{017F}' => {
+                let base = LATIN_BASES[elem as usize - 0xC0];
+                out.push(if base == b'_' { elem } else { base.into() });
+            },
+            _ => out.push(elem),
+        }
+    }
+}
+
+/// A TrieTree of normalized keys, the original keys are kept for display
+/// ===
+/// The keys are normalized by a [`Normalizer`] on insertion and on query,
+/// every normalized key stores the original keys inserted for it
+/// at its stop node, in insertion order.
+/// A normalized key and its nodes are removed with its last original key.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::{NormalizingTrie, CaseFold, StripAccents};
+/// let mut tree = NormalizingTrie::new((CaseFold, StripAccents));
+/// assert!(tree.insert("Café".chars()));
+/// assert!(tree.insert("cafe".chars()));
+/// assert!(tree.insert("Cafeteria".chars()));
+/// assert!(tree.contains("CAFE".chars()));
+///
+/// let spellings: Vec<String> = tree.get("CAFÉ".chars())
+///     .unwrap()
+///     .iter()
+///     .map(String::from_iter)
+///     .collect();
+/// assert_eq!(spellings, ["Café", "cafe"]);
+///
+/// let words: Vec<String> = tree.completions("CAFET".chars())
+///     .map(String::from_iter)
+///     .collect();
+/// assert_eq!(words, ["Cafeteria"]);
+/// ```
+#[derive(Clone)]
+pub struct NormalizingTrie<T, N, S = RandomState> {
+    normalizer: N,
+    /// 规范化后的键组成的树, 终节点存储原始键们
+    arena: Arena<T, Vec<Vec<T>>, S>,
+    /// 原始键的数量
+    count: usize,
+    /// 规范化后的键的数量
+    normalized_count: usize,
+}
+
+impl<T, N> NormalizingTrie<T, N>
+where T: Hash + Eq,
+      N: Normalizer<T>,
+{
+    /// new Self, use the normalizer for every key
+    pub fn new(normalizer: N) -> Self {
+        Self::with_hasher(normalizer, RandomState::default())
+    }
+}
+
+impl<T, N, S> NormalizingTrie<T, N, S> {
+    /// new Self, use the normalizer for every key
+    /// and the given hasher builder for every node
+    pub fn with_hasher(normalizer: N, hasher: S) -> Self {
+        Self {
+            normalizer,
+            arena: Arena::with_capacity_and_hasher(1, hasher),
+            count: 0,
+            normalized_count: 0,
+        }
+    }
+
+    /// return the original keys count
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// return the normalized keys count
+    pub fn normalized_count(&self) -> usize {
+        self.normalized_count
+    }
+
+    /// get the normalizer
+    pub fn normalizer(&self) -> &N {
+        &self.normalizer
+    }
+
+    /// get iterator of all original keys
+    pub fn iter(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.arena.values(ROOT)
+            .flat_map(|(_, originals)| originals.iter().map(Vec::as_slice))
+    }
+}
+
+impl<T, N, S> NormalizingTrie<T, N, S>
+where T: Hash + Eq + Clone,
+      N: Normalizer<T>,
+      S: BuildHasher,
+{
+    /// clear all keys
+    pub fn clear(&mut self) {
+        self.arena.clear();
+        self.count = 0;
+        self.normalized_count = 0;
+    }
+
+    /// insert a key, keep the original key
+    /// - return whether the original key was newly inserted
+    pub fn insert(&mut self, key: impl IntoIterator<Item = T>) -> bool
+    where S: Clone
+    {
+        let original: Vec<T> = key.into_iter().collect();
+        let normalized = self.normalizer.normalize_all(original.iter().cloned());
+        let node = self.arena.get_or_insert_node(normalized);
+        let originals = match &mut self.arena[node].value {
+            Some(originals) => originals,
+            value @ None => {
+                self.normalized_count += 1;
+                value.insert(Vec::new())
+            },
+        };
+        if originals.contains(&original) {
+            return false;
+        }
+        originals.push(original);
+        self.count += 1;
+        true
+    }
+
+    /// Whether the normalized key is in the tree
+    pub fn contains(&self, key: impl IntoIterator<Item = T>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Whether any key starts with the normalized prefix
+    pub fn starts_with(&self, prefix: impl IntoIterator<Item = T>) -> bool {
+        self.arena.query_node(self.normalizer.normalize_all(prefix)).is_some()
+    }
+
+    /// Get the original keys of the normalized key
+    pub fn get(&self, key: impl IntoIterator<Item = T>) -> Option<&[Vec<T>]> {
+        let node = self.arena.query_node(self.normalizer.normalize_all(key))?;
+        self.arena[node].value.as_deref()
+    }
+
+    /// Get the Iterator of the original keys whose normalized keys
+    /// start with the normalized prefix<br/>
+    /// If there is no such key, the iterator is empty
+    pub fn completions(
+        &self,
+        prefix: impl IntoIterator<Item = T>,
+    ) -> impl Iterator<Item = &[T]> + '_ {
+        let node = self.arena.query_node(self.normalizer.normalize_all(prefix));
+        node.into_iter()
+            .flat_map(|node| self.arena.values(node))
+            .flat_map(|(_, originals)| originals.iter().map(Vec::as_slice))
+    }
+
+    /// remove the normalized key
+    /// - return the removed original keys
+    pub fn remove(&mut self, key: impl IntoIterator<Item = T>) -> Option<Vec<Vec<T>>> {
+        let originals = self.arena.remove(self.normalizer.normalize_all(key))?;
+        self.count -= originals.len();
+        self.normalized_count -= 1;
+        Some(originals)
+    }
+
+    /// remove an original key, the normalized key is removed
+    /// with its last original key
+    /// - return whether the original key was in the tree
+    pub fn remove_original(&mut self, key: impl IntoIterator<Item = T>) -> bool {
+        let original: Vec<T> = key.into_iter().collect();
+        let normalized = self.normalizer.normalize_all(original.iter().cloned());
+        let Some(node) = self.arena.query_node(&normalized) else {
+            return false;
+        };
+        let Some(originals) = &mut self.arena[node].value else {
+            return false;
+        };
+        let Some(index) = originals.iter().position(|key| *key == original) else {
+            return false;
+        };
+        originals.remove(index);
+        if originals.is_empty() {
+            self.arena.remove(&normalized);
+            self.normalized_count -= 1;
+        }
+        self.count -= 1;
+        true
+    }
+}
+
+impl<T: Debug, N, S> Debug for NormalizingTrie<T, N, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.arena.values(ROOT))
+            .finish()
+    }
+}
+
+impl<T, N, S, I> Extend<I> for NormalizingTrie<T, N, S>
+where T: Hash + Eq + Clone,
+      N: Normalizer<T>,
+      S: BuildHasher + Clone,
+      I: IntoIterator<Item = T>,
+{
+    fn extend<It: IntoIterator<Item = I>>(&mut self, iter: It) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
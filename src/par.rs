@@ -0,0 +1,215 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hash},
+};
+
+use rayon::prelude::*;
+
+use crate::{trie_node::TrieNode, TrieTree};
+
+/// 按首元素分桶, 每个桶为一个子树
+struct Buckets<T, S> {
+    hasher: S,
+    /// 是否含有空值串
+    has_empty: bool,
+    /// 首元素, 其余部分组成的子树, 以及子树中值的数量
+    buckets: HashMap<T, (TrieNode<T, S>, usize)>,
+}
+
+impl<T, S> Buckets<T, S>
+where T: Hash + Eq,
+      S: BuildHasher + Clone,
+{
+    fn new(hasher: S) -> Self {
+        Self {
+            hasher,
+            has_empty: false,
+            buckets: HashMap::new(),
+        }
+    }
+
+    fn push(mut self, values: impl IntoIterator<Item = T>) -> Self {
+        let mut values = values.into_iter();
+        let Some(first) = values.next() else {
+            self.has_empty = true;
+            return self;
+        };
+        let hasher = &self.hasher;
+        let (node, count) = self.buckets.entry(first)
+            .or_insert_with(|| (TrieNode::with_hasher(hasher.clone()), 0));
+        if node.insert(values) {
+            *count += 1;
+        }
+        self
+    }
+
+    fn merge(mut self, mut other: Self) -> Self {
+        if self.buckets.len() < other.buckets.len() {
+            core::mem::swap(&mut self, &mut other);
+        }
+        self.has_empty |= other.has_empty;
+        for (first, (node, count)) in other.buckets {
+            match self.buckets.get_mut(&first) {
+                Some((target, target_count)) => {
+                    *target_count += count - merge_node(target, node);
+                },
+                None => {
+                    self.buckets.insert(first, (node, count));
+                },
+            }
+        }
+        self
+    }
+}
+
+/// 将`source`合并到`target`中, 返回两者中都存在的值的数量
+fn merge_node<T, S>(target: &mut TrieNode<T, S>, source: TrieNode<T, S>) -> usize
+where T: Hash + Eq,
+      S: BuildHasher,
+{
+    let mut duplicates = 0;
+    let mut stack = vec![(target, source)];
+    while let Some((target, mut source)) = stack.pop() {
+        if source.is_stop() {
+            if target.is_stop() {
+                duplicates += 1;
+            } else {
+                target.set_stop();
+            }
+        }
+        // 只在目标中存在的子树直接移入, 两者都存在的子树继续合并
+        let mut overlap = HashMap::new();
+        for (data, child) in source.childs_mut().drain() {
+            if target.childs().contains_key(&data) {
+                overlap.insert(data, child);
+            } else {
+                target.childs_mut().insert(data, child);
+            }
+        }
+        if overlap.is_empty() {
+            continue;
+        }
+        for (data, child) in target.childs_mut().iter_mut() {
+            if let Some(other) = overlap.remove(data) {
+                stack.push((child, other));
+            }
+        }
+    }
+    duplicates
+}
+
+impl<T, S, I> ParallelExtend<I> for TrieTree<T, S>
+where T: Hash + Eq + Send,
+      S: BuildHasher + Clone + Send + Sync,
+      I: IntoIterator<Item = T> + Send
+{
+    /// Partition the values into subtrees by the first element as they arrive,
+    /// the subtrees built by different threads are merged,
+    /// and then merged into the children of the root in parallel
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// use rayon::prelude::*;
+    /// let mut tree = TrieTree::from_iter([[1, 2, 3]]);
+    /// tree.par_extend((0..100).into_par_iter().map(|i| [i % 7, i]));
+    /// assert_eq!(tree.count(), 101);
+    /// assert!(tree.query([1, 2, 3]));
+    /// assert!(tree.query([2, 9]));
+    /// ```
+    fn par_extend<P>(&mut self, par_iter: P)
+    where P: IntoParallelIterator<Item = I>
+    {
+        let hasher = self.root.childs().hasher().clone();
+        let Buckets { has_empty, buckets, .. } = par_iter.into_par_iter()
+            .fold(|| Buckets::new(hasher.clone()), Buckets::push)
+            .reduce(|| Buckets::new(hasher.clone()), Buckets::merge);
+
+        let mut count = self.count();
+        if has_empty && ! self.root.is_stop() {
+            self.root.set_stop();
+            count += 1;
+        }
+        let subtrees: Vec<_> = buckets.into_iter()
+            .map(|(first, (node, inserted))| {
+                let target = self.root.remove_child(&first);
+                (first, target, node, inserted)
+            })
+            .collect();
+        let subtrees: Vec<_> = subtrees.into_par_iter()
+            .map(|(first, target, node, inserted)| match target {
+                Some(mut target) => {
+                    let duplicates = merge_node(&mut target, node);
+                    (first, target, inserted - duplicates)
+                },
+                None => (first, node, inserted),
+            })
+            .collect();
+        for (first, node, inserted) in subtrees {
+            self.root.childs_mut().insert(first, node);
+            count += inserted;
+        }
+        unsafe { self.set_count(count) }
+    }
+}
+
+impl<T, I> FromParallelIterator<I> for TrieTree<T>
+where T: Hash + Eq + Send,
+      I: IntoIterator<Item = T> + Send
+{
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// use rayon::prelude::*;
+    /// let tree: TrieTree<_> = ["abc", "abd", "bcd"]
+    ///     .into_par_iter()
+    ///     .map(str::chars)
+    ///     .collect();
+    /// assert_eq!(tree, TrieTree::from_iter(["abc", "abd", "bcd"].map(str::chars)));
+    /// ```
+    fn from_par_iter<P>(par_iter: P) -> Self
+    where P: IntoParallelIterator<Item = I>
+    {
+        let mut tree = Self::new();
+        tree.par_extend(par_iter);
+        tree
+    }
+}
+
+impl<T, S> TrieTree<T, S>
+where T: Hash + Eq + Sync,
+      S: BuildHasher + Sync,
+{
+    /// get parallel iterator, the work is split across the children of the root
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// use rayon::prelude::*;
+    /// let tree = TrieTree::from_iter(["abc".chars(), "bcd".chars(), "".chars()]);
+    /// let mut items: Vec<Vec<&char>> = tree.par_iter().collect();
+    /// items.sort();
+    /// assert_eq!(items, [vec![], vec![&'a', &'b', &'c'], vec![&'b', &'c', &'d']]);
+    /// ```
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = Vec<&T>> {
+        let root = &self.root;
+        rayon::iter::once(Vec::new())
+            .filter(move |_| root.is_stop())
+            .chain(root.childs().par_iter()
+                .flat_map_iter(|(data, node)| par_iter_child(data, node)))
+    }
+}
+
+/// 迭代一个子树, 并在每个值前加上该子树的键
+fn par_iter_child<'a, T, S>(
+    data: &'a T,
+    node: &'a TrieNode<T, S>,
+) -> impl Iterator<Item = Vec<&'a T>> {
+    let mut iter = node.iter();
+    std::iter::from_fn(move || {
+        iter.next_op(|values| {
+            let mut res = Vec::with_capacity(values.len() + 1);
+            res.push(data);
+            res.extend(values);
+            res
+        })
+    })
+}
@@ -0,0 +1,426 @@
+use core::{
+    fmt::{self, Display},
+    hash::{BuildHasher, Hash},
+    iter::Peekable,
+    str::{Chars, FromStr},
+};
+use alloc::{string::String, vec};
+
+use crate::{trie_node::TrieNode, TrieTree};
+
+/// The kind of [`ParseError`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind<E> {
+    /// found an unexpected char, `None` is the end of input
+    Unexpected {
+        found: Option<char>,
+        expected: &'static str,
+    },
+    /// invalid escape sequence in a quoted element
+    InvalidEscape,
+    /// the root node is not labeled `/`
+    InvalidRoot,
+    /// failed to parse an element by `FromStr`
+    InvalidElement(E),
+    /// a node has two children with the same element
+    DuplicateChild,
+    /// a node is neither a stop node nor has any children
+    DanglingNode,
+    /// the `count` field does not match the number of stop nodes
+    CountMismatch {
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// Error of parsing the `Debug` text format of [`TrieTree`]
+///
+/// `line` and `column` are 1-based, the column is counted in chars
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<E> {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind<E>,
+}
+
+impl<E: Display> Display for ParseErrorKind<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unexpected { found: Some(found), expected } => {
+                write!(f, "expected {expected}, found {found:?}")
+            },
+            Self::Unexpected { found: None, expected } => {
+                write!(f, "expected {expected}, found end of input")
+            },
+            Self::InvalidEscape => f.write_str("invalid escape sequence"),
+            Self::InvalidRoot => f.write_str("the root node must be `/`"),
+            Self::InvalidElement(e) => write!(f, "invalid element: {e}"),
+            Self::DuplicateChild => f.write_str("duplicate child element"),
+            Self::DanglingNode => {
+                f.write_str("a non-stop node must have children")
+            },
+            Self::CountMismatch { expected, found } => {
+                write!(f, "count is {expected}, but found {found} stop nodes")
+            },
+        }
+    }
+}
+
+impl<E: Display> Display for ParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at line {} column {}", self.kind, self.line, self.column)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: fmt::Debug + Display> std::error::Error for ParseError<E> {}
+
+/// 解析结果
+type ParseResult<T, E> = Result<T, ParseError<E>>;
+
+/// 按字符读取文本, 记录当前的行号与列号
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn error<E>(&self, kind: ParseErrorKind<E>) -> ParseError<E> {
+        ParseError { line: self.line, column: self.column, kind }
+    }
+
+    fn unexpected<E>(&mut self, expected: &'static str) -> ParseError<E> {
+        let found = self.peek();
+        self.error(ParseErrorKind::Unexpected { found, expected })
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(ch)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// 跳过空白后, 如果下一个字符为`ch`则将其消耗
+    fn eat(&mut self, ch: char) -> bool {
+        self.skip_whitespace();
+        let res = self.peek() == Some(ch);
+        if res {
+            self.bump();
+        }
+        res
+    }
+
+    fn expect<E>(&mut self, ch: char, expected: &'static str) -> ParseResult<(), E> {
+        if self.eat(ch) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    /// 跳过空白后读取一个标识符
+    fn ident(&mut self) -> String {
+        self.skip_whitespace();
+        let mut ident = String::new();
+        while let Some(ch) = self.peek().filter(|&ch| ch == '_' || ch.is_alphanumeric()) {
+            ident.push(ch);
+            self.bump();
+        }
+        ident
+    }
+
+    fn expect_ident<E>(&mut self, ident: &str, expected: &'static str) -> ParseResult<(), E> {
+        self.skip_whitespace();
+        let (line, column) = (self.line, self.column);
+        let found = self.ident();
+        if found == ident {
+            Ok(())
+        } else {
+            Err(ParseError {
+                line,
+                column,
+                kind: ParseErrorKind::Unexpected {
+                    found: found.chars().next().or(self.peek()),
+                    expected,
+                },
+            })
+        }
+    }
+
+    /// 读取一个带引号的字面量, 并处理转义
+    fn quoted<E>(&mut self, quote: char) -> ParseResult<String, E> {
+        let mut res = String::new();
+        self.bump();
+        loop {
+            match self.peek() {
+                None => return Err(self.unexpected("closing quote")),
+                Some(ch) if ch == quote => {
+                    self.bump();
+                    return Ok(res);
+                },
+                Some('\\') => {
+                    self.bump();
+                    res.push(self.escape()?);
+                },
+                Some(ch) => {
+                    self.bump();
+                    res.push(ch);
+                },
+            }
+        }
+    }
+
+    /// 读取反斜杠之后的转义序列
+    fn escape<E>(&mut self) -> ParseResult<char, E> {
+        let ch = match self.peek() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('0') => '\0',
+            Some(ch @ ('\\' | '\'' | '"')) => ch,
+            Some('u') => {
+                self.bump();
+                self.expect('{', "`{`")?;
+                let mut code = 0u32;
+                let mut digits = 0;
+                while let Some(digit) = self.peek().and_then(|ch| ch.to_digit(16)) {
+                    self.bump();
+                    code = code.checked_mul(16)
+                        .and_then(|code| code.checked_add(digit))
+                        .ok_or_else(|| self.error(ParseErrorKind::InvalidEscape))?;
+                    digits += 1;
+                }
+                if digits == 0 || self.peek() != Some('}') {
+                    return Err(self.error(ParseErrorKind::InvalidEscape));
+                }
+                char::from_u32(code)
+                    .ok_or_else(|| self.error(ParseErrorKind::InvalidEscape))?
+            },
+            _ => return Err(self.error(ParseErrorKind::InvalidEscape)),
+        };
+        self.bump();
+        Ok(ch)
+    }
+
+    /// 读取未加引号的元素直到匹配的结束括号, 括号可以嵌套
+    fn raw<E>(&mut self, close: char) -> ParseResult<String, E> {
+        let mut res = String::new();
+        let mut depth = 0usize;
+        loop {
+            match self.peek() {
+                None => return Err(self.unexpected("closing bracket")),
+                Some(ch) if depth == 0 && ch == close => {
+                    return Ok(String::from(res.trim()));
+                },
+                Some(ch) => {
+                    match ch {
+                        '(' | '[' | '{' => depth += 1,
+                        ')' | ']' | '}' => {
+                            depth = depth.checked_sub(1)
+                                .ok_or_else(|| self.unexpected("closing bracket"))?;
+                        },
+                        _ => (),
+                    }
+                    self.bump();
+                    res.push(ch);
+                },
+            }
+        }
+    }
+
+    /// 读取节点头部, 如`('a'):`, 返回是否为终节点, 元素文本以及元素的位置
+    fn head<E>(&mut self) -> ParseResult<(bool, String, (usize, usize)), E> {
+        self.skip_whitespace();
+        let (stop, close) = match self.peek() {
+            Some('(') => (false, ')'),
+            Some('[') => (true, ']'),
+            _ => return Err(self.unexpected("`(` or `[`")),
+        };
+        self.bump();
+        self.skip_whitespace();
+        let pos = (self.line, self.column);
+        let data = match self.peek() {
+            Some(quote @ ('\'' | '"')) => self.quoted(quote)?,
+            _ => self.raw(close)?,
+        };
+        self.expect(close, if stop { "`]`" } else { "`)`" })?;
+        self.expect(':', "`:`")?;
+        self.expect('{', "`{`")?;
+        Ok((stop, data, pos))
+    }
+
+    /// 解析一个根节点, 返回节点以及终节点数量
+    fn root<T, S>(&mut self, hasher: &S) -> ParseResult<(TrieNode<T, S>, usize), T::Err>
+    where T: FromStr + Hash + Eq,
+          S: BuildHasher + Clone,
+    {
+        let (stop, data, pos) = self.head()?;
+        if data != "/" {
+            let (line, column) = pos;
+            return Err(ParseError { line, column, kind: ParseErrorKind::InvalidRoot });
+        }
+        let mut count = 0;
+        let mut root = TrieNode::with_hasher(hasher.clone());
+        if stop {
+            root.set_stop();
+            count += 1;
+        }
+        let mut stack = vec![Frame { node: root, data: None, pos, need_sep: false }];
+        loop {
+            if self.eat('}') {
+                let Frame { node, data, pos: (line, column), .. } = stack.pop().unwrap();
+                let Some(parent) = stack.last_mut() else {
+                    // 空树的根节点可以没有子节点
+                    break Ok((node, count));
+                };
+                if node.can_remove() {
+                    return Err(ParseError { line, column, kind: ParseErrorKind::DanglingNode });
+                }
+                if parent.node.childs_mut().insert(data.unwrap(), node).is_some() {
+                    return Err(ParseError { line, column, kind: ParseErrorKind::DuplicateChild });
+                }
+                parent.need_sep = true;
+                continue;
+            }
+            let frame = stack.last_mut().unwrap();
+            if frame.need_sep {
+                // 允许尾随逗号, 在下一轮循环中检查`}`
+                frame.need_sep = false;
+                self.expect(',', "`,` or `}`")?;
+                continue;
+            }
+            let (stop, data, pos) = self.head()?;
+            let data = data.parse::<T>()
+                .map_err(|e| {
+                    let (line, column) = pos;
+                    ParseError { line, column, kind: ParseErrorKind::InvalidElement(e) }
+                })?;
+            let mut node = TrieNode::with_hasher(hasher.clone());
+            if stop {
+                node.set_stop();
+                count += 1;
+            }
+            stack.push(Frame { node, data: Some(data), pos, need_sep: false });
+        }
+    }
+}
+
+/// 正在解析的节点
+struct Frame<T, S> {
+    node: TrieNode<T, S>,
+    /// 该节点在父节点中的元素, 根节点为`None`
+    data: Option<T>,
+    /// 该节点元素的位置
+    pos: (usize, usize),
+    /// 是否需要在下一个子节点之前读取逗号
+    need_sep: bool,
+}
+
+impl<T, S> TrieTree<T, S>
+where T: FromStr + Hash + Eq,
+      S: BuildHasher + Clone,
+{
+    /// Parse the `Debug` text format with the given hasher builder
+    /// - see [`FromStr`] implementation
+    pub fn from_str_with_hasher(s: &str, hasher: S) -> Result<Self, ParseError<T::Err>> {
+        let mut parser = Parser::new(s);
+        parser.skip_whitespace();
+        let expected_count = if parser.peek().is_some_and(char::is_alphabetic) {
+            parser.expect_ident("TrieTree", "`TrieTree` or root node")?;
+            parser.expect('{', "`{`")?;
+            parser.expect_ident("count", "`count`")?;
+            parser.expect(':', "`:`")?;
+            parser.skip_whitespace();
+            let pos = (parser.line, parser.column);
+            let count = parser.ident();
+            let count = count.parse::<usize>().map_err(|_| ParseError {
+                line: pos.0,
+                column: pos.1,
+                kind: ParseErrorKind::Unexpected {
+                    found: count.chars().next(),
+                    expected: "count number",
+                },
+            })?;
+            parser.expect(',', "`,`")?;
+            parser.expect_ident("root", "`root`")?;
+            parser.expect(':', "`:`")?;
+            Some((count, pos))
+        } else {
+            None
+        };
+        let (root, count) = parser.root(&hasher)?;
+        if let Some((expected, (line, column))) = expected_count {
+            parser.eat(',');
+            parser.expect('}', "`}`")?;
+            if expected != count {
+                return Err(ParseError {
+                    line,
+                    column,
+                    kind: ParseErrorKind::CountMismatch { expected, found: count },
+                });
+            }
+        }
+        parser.skip_whitespace();
+        if parser.peek().is_some() {
+            return Err(parser.unexpected("end of input"));
+        }
+        Ok(Self { root, count })
+    }
+}
+
+impl<T, S> FromStr for TrieTree<T, S>
+where T: FromStr + Hash + Eq,
+      S: BuildHasher + Clone + Default,
+{
+    type Err = ParseError<T::Err>;
+
+    /// Parse the `Debug` text format, such as `(/): { ('a'): { ['c']: {} } }`
+    /// - the `TrieTree { count: N, root: ... }` wrapper is optional,
+    ///   if present, the count is checked
+    /// - quoted elements such as `'a'` and `"abc"` are unescaped
+    ///   before being parsed by `T::from_str`
+    /// - whitespace is ignored and trailing commas are allowed
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, ParseErrorKind};
+    /// let tree: TrieTree<char> = "(/): { ('a'): { ['b']: {}, ['c']: {} } }".parse().unwrap();
+    /// assert_eq!(tree, TrieTree::from_iter(["ab".chars(), "ac".chars()]));
+    /// assert_eq!(tree.count(), 2);
+    ///
+    /// let nums: TrieTree<i32> = format!("{:#?}", TrieTree::from_iter([[1, 2], [3, 4]]))
+    ///     .parse()
+    ///     .unwrap();
+    /// assert_eq!(nums, TrieTree::from_iter([[1, 2], [3, 4]]));
+    ///
+    /// let err = "(/): {\n    ['a']: {},\n    (bc): {}\n}".parse::<TrieTree<char>>().unwrap_err();
+    /// assert_eq!((err.line, err.column), (3, 6));
+    /// assert!(matches!(err.kind, ParseErrorKind::InvalidElement(_)));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with_hasher(s, S::default())
+    }
+}
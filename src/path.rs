@@ -0,0 +1,229 @@
+use std::{
+    ffi::{OsStr, OsString},
+    fmt::{self, Debug},
+    fs, io,
+    hash::BuildHasher,
+    path::{Path, PathBuf},
+};
+
+use crate::{collections::RandomState, KeyIter, KeyTrie, TrieKey, TrieTree};
+
+/// An index of filesystem paths, split by [`Path::components`]
+/// ===
+/// # Examples
+/// ```
+/// # use fast_trie_tree::PathTrie;
+/// # use std::path::{Path, PathBuf};
+/// let mut paths = PathTrie::new();
+/// assert!(paths.insert("/usr/bin/ls"));
+/// assert!(paths.insert("/usr/bin/cat"));
+/// assert!(paths.insert("/usr/lib"));
+///
+/// let mut files: Vec<PathBuf> = paths.files_under("/usr/bin").collect();
+/// files.sort();
+/// assert_eq!(files, [Path::new("/usr/bin/cat"), Path::new("/usr/bin/ls")]);
+///
+/// assert_eq!(paths.nearest_ancestor("/usr/lib/libc.so"), Some(PathBuf::from("/usr/lib")));
+/// assert_eq!(paths.nearest_ancestor("/etc"), None);
+/// ```
+pub struct PathTrie<S = RandomState> {
+    tree: KeyTrie<Path, S>,
+}
+
+impl PathTrie {
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for PathTrie {
+    fn default() -> Self {
+        Self { tree: KeyTrie::new() }
+    }
+}
+
+impl<S> PathTrie<S> {
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self { tree: KeyTrie::with_hasher(hasher) }
+    }
+
+    /// return paths count
+    pub fn count(&self) -> usize {
+        self.tree.count()
+    }
+
+    /// get the inner tree of path components
+    pub fn as_tree(&self) -> &TrieTree<OsString, S> {
+        self.tree.as_tree()
+    }
+}
+
+impl<S> PathTrie<S>
+where S: BuildHasher,
+{
+    /// get iterator of all paths
+    pub fn iter(&self) -> KeyIter<'_, Path, S> {
+        self.tree.iter()
+    }
+
+    /// clear all paths
+    pub fn clear(&mut self)
+    where S: Clone
+    {
+        self.tree.clear();
+    }
+
+    /// insert a path
+    /// - return whether the insertion path was successful
+    pub fn insert(&mut self, path: impl AsRef<Path>) -> bool
+    where S: Clone
+    {
+        self.tree.insert(path.as_ref())
+    }
+
+    /// Whether the path is in the index
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        self.tree.query(path.as_ref())
+    }
+
+    /// remove a path
+    pub fn remove(&mut self, path: impl AsRef<Path>) -> bool {
+        self.tree.remove(path.as_ref())
+    }
+
+    /// Get the Iterator of the indexed paths under the directory,
+    /// including the directory itself if it is indexed<br/>
+    /// If there is no such path, the iterator is empty
+    pub fn files_under(&self, dir: impl AsRef<Path>) -> KeyIter<'_, Path, S> {
+        self.tree.query_iter(dir.as_ref()).unwrap_or_default()
+    }
+
+    /// Get the nearest indexed ancestor of the path,
+    /// the path itself is its nearest ancestor if it is indexed
+    pub fn nearest_ancestor(&self, path: impl AsRef<Path>) -> Option<PathBuf> {
+        let components: Vec<_> = path.as_ref().elems().collect();
+        let len = self.as_tree().longest_prefix(&components)?;
+        Some(Path::from_elems(components[..len].iter()))
+    }
+
+    /// Get the Iterator of the immediate children names of the directory<br/>
+    /// If the directory is not a prefix of any indexed path, return `None`
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::PathTrie;
+    /// # use std::ffi::OsStr;
+    /// let paths = PathTrie::from_iter(["src/lib.rs", "src/path.rs", "src/trie_node/mod.rs"]);
+    /// let mut names: Vec<&OsStr> = paths.children("src").unwrap().collect();
+    /// names.sort();
+    /// assert_eq!(names, ["lib.rs", "path.rs", "trie_node"]);
+    /// assert!(paths.children("target").is_none());
+    /// ```
+    pub fn children(
+        &self,
+        dir: impl AsRef<Path>,
+    ) -> Option<impl Iterator<Item = &OsStr> + '_> {
+        let node = self.as_tree().root.query_node(dir.as_ref().elems())?;
+        Some(node.childs().keys().map(OsString::as_os_str))
+    }
+
+    /// Walk the local directory tree, insert the paths of all non-directory
+    /// entries, the symbolic links are inserted but not followed<br/>
+    /// An unreadable directory or entry is skipped, and the walk continues
+    /// - return the number of newly inserted paths,
+    ///   and the errors of the skipped directories and entries
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::PathTrie;
+    /// let mut paths = PathTrie::new();
+    /// let (inserted, errors) = paths.walk_dir("/nonexistent/fast_trie_tree");
+    /// assert_eq!(inserted, 0);
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn walk_dir(&mut self, root: impl AsRef<Path>) -> (usize, Vec<io::Error>)
+    where S: Clone
+    {
+        let mut inserted = 0;
+        let mut errors = Vec::new();
+        let mut dirs = vec![root.as_ref().to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            let entries = match fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                },
+            };
+            for entry in entries {
+                let file_type = entry.and_then(|entry| {
+                    let file_type = entry.file_type()?;
+                    Ok((entry, file_type))
+                });
+                let (entry, file_type) = match file_type {
+                    Ok(entry) => entry,
+                    Err(e) => {
+                        errors.push(e);
+                        continue;
+                    },
+                };
+                if file_type.is_dir() {
+                    dirs.push(entry.path());
+                } else if self.insert(entry.path()) {
+                    inserted += 1;
+                }
+            }
+        }
+        (inserted, errors)
+    }
+
+    /// Shrink each `HashMap` in the index
+    pub fn shrink_to_fit(&mut self) {
+        self.tree.shrink_to_fit();
+    }
+}
+
+impl<S> Clone for PathTrie<S>
+where S: BuildHasher + Clone,
+{
+    fn clone(&self) -> Self {
+        Self { tree: self.tree.clone() }
+    }
+}
+
+impl<S> Debug for PathTrie<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tree.fmt(f)
+    }
+}
+
+impl<P: AsRef<Path>> FromIterator<P> for PathTrie {
+    fn from_iter<I: IntoIterator<Item = P>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<P, S> Extend<P> for PathTrie<S>
+where P: AsRef<Path>,
+      S: BuildHasher + Clone,
+{
+    fn extend<I: IntoIterator<Item = P>>(&mut self, iter: I) {
+        for path in iter {
+            self.insert(path);
+        }
+    }
+}
+
+impl<'a, S> IntoIterator for &'a PathTrie<S>
+where S: BuildHasher,
+{
+    type Item = PathBuf;
+    type IntoIter = KeyIter<'a, Path, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,446 @@
+use core::{
+    fmt::{self, Display},
+    hash::BuildHasher,
+};
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{arena::{Arena, ROOT}, collections::RandomState};
+
+/// Error of [`Router::insert`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouteError {
+    /// a parameter or wildcard segment without name,
+    /// or a wildcard segment that is not the last segment
+    InvalidSegment {
+        route: String,
+        segment: String,
+    },
+    /// a parameter name appears more than once in the route
+    DuplicateParam {
+        route: String,
+        name: String,
+    },
+    /// the route conflicts with an inserted route,
+    /// such as the same route, or another parameter name at the same position
+    Conflict {
+        route: String,
+        existing: String,
+    },
+}
+
+impl Display for RouteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSegment { route, segment } => {
+                write!(f, "invalid segment {segment:?} in route {route:?}")
+            },
+            Self::DuplicateParam { route, name } => {
+                write!(f, "duplicate parameter {name:?} in route {route:?}")
+            },
+            Self::Conflict { route, existing } => {
+                write!(f, "route {route:?} conflicts with {existing:?}")
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RouteError {}
+
+/// A matched route, see [`Router::at`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouteMatch<'r, 'p, V> {
+    /// value of the matched route
+    pub value: &'r V,
+    /// the matched route pattern, such as `/users/:id`
+    pub route: &'r str,
+    /// captured parameters in order, the name without `:` or `*`
+    pub params: Vec<(&'r str, &'p str)>,
+}
+
+impl<'p, V> RouteMatch<'_, 'p, V> {
+    /// get the captured parameter by name
+    pub fn param(&self, name: &str) -> Option<&'p str> {
+        self.params.iter()
+            .find(|(param, _)| *param == name)
+            .map(|&(_, value)| value)
+    }
+}
+
+/// 路由模式中的一个段
+#[derive(Debug, Clone, Copy)]
+enum Segment<'a> {
+    Static(&'a str),
+    Param(&'a str),
+    Wildcard(&'a str),
+}
+
+/// 按照`/`切分, 忽略空段, 同时产生每段的起始字节偏移
+fn split_path_indices(path: &str) -> impl Iterator<Item = (usize, &str)> {
+    path.split('/')
+        .scan(0, |start, segment| {
+            let segment_start = *start;
+            *start += segment.len() + 1;
+            Some((segment_start, segment))
+        })
+        .filter(|(_, segment)| ! segment.is_empty())
+}
+
+/// 按照`/`切分, 忽略空段
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    split_path_indices(path).map(|(_, segment)| segment)
+}
+
+/// 解析并检查路由模式
+fn parse_route(route: &str) -> Result<Vec<Segment<'_>>, RouteError> {
+    let mut segments = Vec::new();
+    let mut names: Vec<&str> = Vec::new();
+    let mut iter = split_path(route).peekable();
+    while let Some(segment) = iter.next() {
+        let invalid = || RouteError::InvalidSegment {
+            route: route.into(),
+            segment: segment.into(),
+        };
+        let (name, parsed) = if let Some(name) = segment.strip_prefix(':') {
+            (name, Segment::Param(name))
+        } else if let Some(name) = segment.strip_prefix('*') {
+            if iter.peek().is_some() {
+                return Err(invalid());
+            }
+            (name, Segment::Wildcard(name))
+        } else {
+            segments.push(Segment::Static(segment));
+            continue;
+        };
+        if name.is_empty() {
+            return Err(invalid());
+        }
+        if names.contains(&name) {
+            return Err(RouteError::DuplicateParam { route: route.into(), name: name.into() });
+        }
+        names.push(name);
+        segments.push(parsed);
+    }
+    Ok(segments)
+}
+
+/// 参数段或通配段, 记录引入该段的路由, 用于报告冲突
+struct Named<T> {
+    name: String,
+    route: String,
+    data: T,
+}
+
+/// 节点上除静态段以外的数据, 静态段为节点在竞技场中的子节点
+struct RouteData<V> {
+    /// 在该节点结束的路由以及值
+    value: Option<(String, V)>,
+    /// 参数段子节点的下标
+    param: Option<Named<usize>>,
+    /// 通配段总是最后一段, 所以直接存储值
+    wildcard: Option<Named<V>>,
+}
+
+impl<V> Default for RouteData<V> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            param: None,
+            wildcard: None,
+        }
+    }
+}
+
+impl<V> RouteData<V> {
+    fn is_empty(&self) -> bool {
+        self.value.is_none() && self.param.is_none() && self.wildcard.is_none()
+    }
+}
+
+/// 匹配时正在尝试的节点
+struct Frame {
+    node: usize,
+    depth: usize,
+    /// 下一个要尝试的分支: 静态段, 参数段, 通配段
+    stage: u8,
+    /// 进入该节点之前已捕获的参数数量
+    params_len: usize,
+}
+
+/// An HTTP-style route matcher on path segments
+/// ===
+/// - static segments, such as `users`
+/// - named parameter segments, such as `:id`, match any one segment
+/// - catch-all tails, such as `*rest`, match one or more remaining segments
+///
+/// When several routes match a path, static segments take precedence over
+/// parameters, and parameters over catch-all tails, segment by segment.
+/// Empty segments are ignored, so `/a//b/` is the same as `/a/b`.
+/// Removing a route frees the segments no other route uses for later insertions.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::Router;
+/// let mut router = Router::new();
+/// router.insert("/users/:id", "user").unwrap();
+/// router.insert("/users/me", "me").unwrap();
+/// router.insert("/users/:id/posts", "posts").unwrap();
+/// router.insert("/static/*path", "static").unwrap();
+///
+/// let m = router.at("/users/42/posts").unwrap();
+/// assert_eq!(*m.value, "posts");
+/// assert_eq!(m.route, "/users/:id/posts");
+/// assert_eq!(m.param("id"), Some("42"));
+///
+/// assert_eq!(*router.at("/users/me").unwrap().value, "me");
+/// assert_eq!(router.at("/static/css/main.css").unwrap().param("path"), Some("css/main.css"));
+/// assert!(router.at("/static").is_none());
+/// assert!(router.insert("/users/:name", "conflict").is_err());
+/// ```
+pub struct Router<V, S = RandomState> {
+    /// 静态段为节点的子节点, 节点的值只在有数据时为`Some`
+    arena: Arena<String, RouteData<V>, S>,
+    count: usize,
+}
+
+impl<V> Router<V> {
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<V> Default for Router<V> {
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<V, S> Router<V, S> {
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            arena: Arena::with_capacity_and_hasher(1, hasher),
+            count: 0,
+        }
+    }
+
+    /// return routes count
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    fn data(&self, node: usize) -> Option<&RouteData<V>> {
+        self.arena[node].value.as_ref()
+    }
+
+    fn data_mut(&mut self, node: usize) -> &mut RouteData<V> {
+        self.arena[node].value.get_or_insert_with(RouteData::default)
+    }
+
+    /// 数据为空时将节点的值设置为`None`, 使其可以被清理
+    fn tidy(&mut self, node: usize) {
+        let value = &mut self.arena[node].value;
+        if value.as_ref().is_some_and(RouteData::is_empty) {
+            *value = None;
+        }
+    }
+
+    /// get iterator of all routes and values, in unspecified order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        let mut nodes = vec![ROOT];
+        let mut wildcard: Option<&Named<V>> = None;
+        core::iter::from_fn(move || loop {
+            if let Some(wildcard) = wildcard.take() {
+                return Some((&wildcard.route[..], &wildcard.data));
+            }
+            let node = nodes.pop()?;
+            nodes.extend(self.arena[node].childs.values());
+            let Some(data) = self.data(node) else { continue };
+            nodes.extend(data.param.as_ref().map(|param| param.data));
+            wildcard = data.wildcard.as_ref();
+            if let Some((route, value)) = &data.value {
+                return Some((&route[..], value));
+            }
+        })
+    }
+}
+
+impl<V, S: BuildHasher> Router<V, S> {
+    /// 沿着路由模式的一段向下走, 参数名不同时视为同一个节点
+    fn child(&self, node: usize, segment: Segment<'_>) -> Option<usize> {
+        match segment {
+            Segment::Static(s) => self.arena[node].childs.get(s).copied(),
+            Segment::Param(_) => self.data(node)?.param.as_ref().map(|param| param.data),
+            Segment::Wildcard(_) => None,
+        }
+    }
+
+    /// 检查插入的冲突, 检查通过后插入不会失败
+    fn check_conflict(&self, route: &str, segments: &[Segment<'_>]) -> Result<(), RouteError> {
+        let conflict = |existing: &str| RouteError::Conflict {
+            route: route.into(),
+            existing: existing.into(),
+        };
+        let mut node = ROOT;
+        for &segment in segments {
+            let data = self.data(node);
+            match segment {
+                Segment::Param(name) => match data.and_then(|data| data.param.as_ref()) {
+                    Some(param) if param.name != name => return Err(conflict(&param.route)),
+                    _ => (),
+                },
+                Segment::Wildcard(_) => {
+                    return match data.and_then(|data| data.wildcard.as_ref()) {
+                        Some(wildcard) => Err(conflict(&wildcard.route)),
+                        None => Ok(()),
+                    };
+                },
+                Segment::Static(_) => (),
+            }
+            match self.child(node, segment) {
+                Some(child) => node = child,
+                None => return Ok(()),
+            }
+        }
+        match self.data(node).and_then(|data| data.value.as_ref()) {
+            Some((existing, _)) => Err(conflict(existing)),
+            None => Ok(()),
+        }
+    }
+
+    /// insert a route
+    /// - return error if the route is invalid or conflicts with an inserted route,
+    ///   the router is not changed on error
+    pub fn insert(&mut self, route: &str, value: V) -> Result<(), RouteError>
+    where S: Clone
+    {
+        let segments = parse_route(route)?;
+        self.check_conflict(route, &segments)?;
+        let mut node = ROOT;
+        for segment in segments {
+            node = match segment {
+                Segment::Static(s) => self.arena.get_or_insert_child(node, s.into()),
+                Segment::Param(name) => match self.child(node, segment) {
+                    Some(child) => child,
+                    None => {
+                        let child = self.arena.alloc_node();
+                        self.data_mut(node).param = Some(Named {
+                            name: name.into(),
+                            route: route.into(),
+                            data: child,
+                        });
+                        child
+                    },
+                },
+                Segment::Wildcard(name) => {
+                    self.data_mut(node).wildcard = Some(Named {
+                        name: name.into(),
+                        route: route.into(),
+                        data: value,
+                    });
+                    self.count += 1;
+                    return Ok(());
+                },
+            };
+        }
+        self.data_mut(node).value = Some((route.into(), value));
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Match the path, return the value of the matched route
+    /// with the highest precedence and the captured parameters
+    pub fn at<'p>(&self, path: &'p str) -> Option<RouteMatch<'_, 'p, V>> {
+        let segments: Vec<(usize, &str)> = split_path_indices(path).collect();
+        let mut params = Vec::new();
+        // 使用显式栈回溯, 静态段优先于参数段, 参数段优先于通配段
+        let mut stack = vec![Frame { node: ROOT, depth: 0, stage: 0, params_len: 0 }];
+        while let Some(frame) = stack.last_mut() {
+            let (node, depth) = (frame.node, frame.depth);
+            let segment = segments.get(depth).copied();
+            let data = self.data(node);
+            frame.stage += 1;
+            match (frame.stage, segment) {
+                (1, None) => if let Some((route, value)) = data.and_then(|data| data.value.as_ref()) {
+                    return Some(RouteMatch { value, route, params });
+                },
+                (1, Some((_, segment))) => if let Some(&child) = self.arena[node].childs.get(segment) {
+                    let params_len = params.len();
+                    stack.push(Frame { node: child, depth: depth + 1, stage: 0, params_len });
+                },
+                (2, Some((_, segment))) => if let Some(param) = data.and_then(|data| data.param.as_ref()) {
+                    let params_len = params.len();
+                    params.push((&*param.name, segment));
+                    stack.push(Frame { node: param.data, depth: depth + 1, stage: 0, params_len });
+                },
+                (3, Some((start, _))) => if let Some(wildcard) = data.and_then(|data| data.wildcard.as_ref()) {
+                    params.push((&*wildcard.name, &path[start..]));
+                    return Some(RouteMatch {
+                        value: &wildcard.data,
+                        route: &wildcard.route,
+                        params,
+                    });
+                },
+                (2 | 3, None) => (),
+                _ => {
+                    params.truncate(frame.params_len);
+                    stack.pop();
+                },
+            }
+        }
+        None
+    }
+
+    /// remove the route, the parameter names may be different
+    /// from the inserted route
+    /// - return the value of the removed route
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::Router;
+    /// let mut router = Router::new();
+    /// router.insert("/users/:id", 1).unwrap();
+    /// assert_eq!(router.remove("/users/:id"), Some(1));
+    /// assert_eq!(router.remove("/users/:id"), None);
+    /// assert!(router.insert("/users/:name", 2).is_ok());
+    /// ```
+    pub fn remove(&mut self, route: &str) -> Option<V> {
+        let segments = parse_route(route).ok()?;
+        let (last, path) = match segments.split_last() {
+            Some((&Segment::Wildcard(name), path)) => (Some(name), path),
+            _ => (None, &segments[..]),
+        };
+        // 记录路径上的父节点以及段
+        let mut parents = Vec::with_capacity(path.len());
+        let mut node = ROOT;
+        for &segment in path {
+            let child = self.child(node, segment)?;
+            parents.push((node, segment));
+            node = child;
+        }
+        let data = self.arena[node].value.as_mut()?;
+        let value = match last {
+            Some(_) => data.wildcard.take()?.data,
+            None => data.value.take()?.1,
+        };
+        self.count -= 1;
+        self.tidy(node);
+
+        // 从下至上清理悬垂节点, 从父节点断开后放入空闲列表
+        while let Some((parent, segment)) = parents.pop() {
+            if ! self.arena[node].can_remove() {
+                break;
+            }
+            match segment {
+                Segment::Static(s) => { self.arena[parent].childs.remove(s); },
+                Segment::Param(_) => self.data_mut(parent).param = None,
+                Segment::Wildcard(_) => unreachable!(),
+            }
+            self.tidy(parent);
+            self.arena.free_node(node);
+            node = parent;
+        }
+        Some(value)
+    }
+}
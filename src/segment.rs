@@ -0,0 +1,103 @@
+use core::{hash::BuildHasher, ops::Range};
+use alloc::{vec, vec::Vec};
+
+use crate::TrieTree;
+
+/// The maximum matching mode of [`TrieTree::segment`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SegmentMode {
+    /// Take the longest word from the start of the text
+    #[default]
+    ForwardMax,
+    /// Take the longest word from the end of the text
+    BackwardMax,
+    /// Run both, prefer the result with fewer tokens,
+    /// then fewer single `char` tokens, then the backward result
+    Bidirectional,
+}
+
+impl<S: BuildHasher> TrieTree<char, S> {
+    /// Segment the text into words of the tree by maximum matching,
+    /// the `char`s not covered by any word are single tokens
+    /// - return the byte ranges of the tokens, in text order
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::{TrieTree, SegmentMode};
+    /// let tree = TrieTree::from_iter(
+    ///     ["研究", "研究生", "生命", "命", "的", "起源"].map(str::chars));
+    /// let text = "研究生命的起源";
+    /// let words = |mode| -> Vec<&str> {
+    ///     tree.segment(text, mode).into_iter().map(|span| &text[span]).collect()
+    /// };
+    /// assert_eq!(words(SegmentMode::ForwardMax),    ["研究生", "命", "的", "起源"]);
+    /// assert_eq!(words(SegmentMode::BackwardMax),   ["研究", "生命", "的", "起源"]);
+    /// assert_eq!(words(SegmentMode::Bidirectional), ["研究", "生命", "的", "起源"]);
+    /// ```
+    pub fn segment(&self, text: &str, mode: SegmentMode) -> Vec<Range<usize>> {
+        // 每个字符的字节偏移, 末尾附加文本长度
+        let mut offsets: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        offsets.push(text.len());
+        let chars: Vec<char> = text.chars().collect();
+        let spans = |bounds: Vec<(usize, usize)>| -> Vec<Range<usize>> {
+            bounds.into_iter()
+                .map(|(start, end)| offsets[start]..offsets[end])
+                .collect()
+        };
+        match mode {
+            SegmentMode::ForwardMax => spans(self.forward_max(&chars)),
+            SegmentMode::BackwardMax => spans(self.backward_max(&chars)),
+            SegmentMode::Bidirectional => {
+                let forward = self.forward_max(&chars);
+                let backward = self.backward_max(&chars);
+                let singles = |tokens: &[(usize, usize)]| {
+                    tokens.iter().filter(|(start, end)| end - start == 1).count()
+                };
+                let key = |tokens: &[(usize, usize)]| (tokens.len(), singles(tokens));
+                if key(&forward) < key(&backward) {
+                    spans(forward)
+                } else {
+                    spans(backward)
+                }
+            },
+        }
+    }
+
+    /// 正向最大匹配, 返回字符下标区间
+    fn forward_max(&self, chars: &[char]) -> Vec<(usize, usize)> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        while start < chars.len() {
+            let len = self.root.longest_prefix(chars[start..].iter())
+                .filter(|&len| len != 0)
+                .unwrap_or(1);
+            tokens.push((start, start + len));
+            start += len;
+        }
+        tokens
+    }
+
+    /// 逆向最大匹配, 返回字符下标区间
+    fn backward_max(&self, chars: &[char]) -> Vec<(usize, usize)> {
+        // 以每个位置结尾的最长词的起始位置, 由各个位置开始的正向遍历得到
+        let mut word_starts = vec![None; chars.len() + 1];
+        for start in 0..chars.len() {
+            let mut node = &self.root;
+            for (end, ch) in (start + 1..).zip(&chars[start..]) {
+                let Some(child) = node.get_child(ch) else { break };
+                node = child;
+                if node.is_stop() {
+                    word_starts[end].get_or_insert(start);
+                }
+            }
+        }
+        let mut tokens = Vec::new();
+        let mut end = chars.len();
+        while end > 0 {
+            let start = word_starts[end].unwrap_or(end - 1);
+            tokens.push((start, end));
+            end = start;
+        }
+        tokens.reverse();
+        tokens
+    }
+}
@@ -0,0 +1,125 @@
+use core::mem::size_of;
+use alloc::{vec, vec::Vec};
+
+use crate::{trie_node::TrieNode, TrieTree};
+
+/// Structural statistics of a [`TrieTree`], see [`TrieTree::stats`]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TrieStats {
+    /// number of nodes, including the root
+    pub node_count: usize,
+    /// number of stop nodes, equal to [`TrieTree::count`]
+    pub stop_count: usize,
+    /// the depth of the deepest node, the root depth is zero
+    pub max_depth: usize,
+    /// average depth of stop nodes, that is the average values length
+    pub avg_depth: f64,
+    /// `branching[n]` is the number of nodes that have `n` children
+    pub branching: Vec<usize>,
+    /// number of non-stop nodes that have only one child
+    pub single_child_nodes: usize,
+    /// number of maximal chains made up of single child nodes,
+    /// each chain can be merged into one edge by a compressed representation
+    pub single_child_chains: usize,
+    /// sum of the children `HashMap` lengths
+    pub childs_len: usize,
+    /// sum of the children `HashMap` capacities
+    pub childs_capacity: usize,
+    /// estimated heap bytes used by all children `HashMap`s
+    pub heap_bytes: usize,
+}
+
+impl TrieStats {
+    /// average number of children of non-leaf nodes
+    pub fn avg_branching(&self) -> f64 {
+        let inner = self.node_count - self.branching.first().copied().unwrap_or_default();
+        if inner == 0 {
+            0.0
+        } else {
+            self.childs_len as f64 / inner as f64
+        }
+    }
+
+    /// ratio of the unused `HashMap` capacity, a high ratio means that
+    /// [`TrieTree::shrink_to_fit`] can release a lot of memory
+    pub fn unused_capacity_ratio(&self) -> f64 {
+        if self.childs_capacity == 0 {
+            0.0
+        } else {
+            1.0 - self.childs_len as f64 / self.childs_capacity as f64
+        }
+    }
+}
+
+/// 估算哈希表的堆内存占用
+/// 按照`hashbrown`的布局: 桶数量为二的幂, 每个桶有一个控制字节, 外加一组控制字节
+fn map_heap_bytes(capacity: usize, entry_size: usize) -> usize {
+    const GROUP_WIDTH: usize = 16;
+    if capacity == 0 {
+        return 0;
+    }
+    let buckets = if capacity < 4 {
+        4
+    } else if capacity < 8 {
+        8
+    } else {
+        (capacity * 8 / 7).next_power_of_two()
+    };
+    buckets * entry_size + buckets + GROUP_WIDTH
+}
+
+impl<T, S> TrieTree<T, S> {
+    /// Collect the structural statistics and estimated memory usage
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::TrieTree;
+    /// let tree = TrieTree::from_iter(["abc".chars(), "abd".chars(), "x".chars()]);
+    /// let stats = tree.stats();
+    /// assert_eq!(stats.node_count, 6);
+    /// assert_eq!(stats.stop_count, 3);
+    /// assert_eq!(stats.max_depth, 3);
+    /// assert_eq!(stats.avg_depth, 7.0 / 3.0);
+    /// assert_eq!(stats.branching, [3, 1, 2]);
+    /// assert_eq!(stats.single_child_nodes, 1);
+    /// assert_eq!(stats.single_child_chains, 1);
+    /// assert_eq!(stats.childs_len, 5);
+    /// assert!(stats.childs_capacity >= 5);
+    /// assert!(stats.heap_bytes > 0);
+    /// ```
+    pub fn stats(&self) -> TrieStats {
+        let entry_size = size_of::<(T, TrieNode<T, S>)>();
+        let mut stats = TrieStats::default();
+        let mut depth_sum = 0;
+        // 节点, 深度, 父节点是否为单子节点
+        let mut stack = vec![(&self.root, 0, false)];
+        while let Some((node, depth, in_chain)) = stack.pop() {
+            let childs = node.childs();
+            stats.node_count += 1;
+            stats.max_depth = stats.max_depth.max(depth);
+            if node.is_stop() {
+                stats.stop_count += 1;
+                depth_sum += depth;
+            }
+            if stats.branching.len() <= childs.len() {
+                stats.branching.resize(childs.len() + 1, 0);
+            }
+            stats.branching[childs.len()] += 1;
+            let is_single = ! node.is_stop() && childs.len() == 1;
+            if is_single {
+                stats.single_child_nodes += 1;
+                if ! in_chain {
+                    stats.single_child_chains += 1;
+                }
+            }
+            stats.childs_len += childs.len();
+            stats.childs_capacity += childs.capacity();
+            stats.heap_bytes += map_heap_bytes(childs.capacity(), entry_size);
+            stack.extend(childs.values()
+                .map(|child| (child, depth + 1, is_single)));
+        }
+        if stats.stop_count != 0 {
+            stats.avg_depth = depth_sum as f64 / stats.stop_count as f64;
+        }
+        stats
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    hash::BuildHasher,
+    io::{self, BufRead, ErrorKind},
+};
+
+use crate::{collections::RandomState, AhoCorasick};
+
+/// A match in a stream, by the absolute byte offsets, see [`StreamScanner`]
+///
+/// The offsets are `u64`, so the streams longer than `usize::MAX` bytes
+/// are supported on any target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StreamMatch {
+    /// the offset of the first byte
+    pub start: u64,
+    /// the offset after the last byte
+    pub end: u64,
+}
+
+/// Scan a [`BufRead`] with an [`AhoCorasick`] automaton of bytes,
+/// without loading the whole input
+/// ===
+/// The matches are reported by the absolute byte offsets in the input,
+/// in the order of [`AhoCorasick::find_iter`].
+/// The automaton state is kept between the buffers,
+/// so the matches spanning buffer boundaries are found.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::{TrieTree, StreamScanner, StreamMatch};
+/// # use std::io::BufReader;
+/// let tree = TrieTree::from_iter(["ERROR".bytes(), "WARN".bytes()]);
+/// let ac = tree.to_aho_corasick();
+/// let log = b"INFO ok\nWARN disk\nERROR io\n";
+/// // a tiny buffer, the matches span the buffer boundaries
+/// let reader = BufReader::with_capacity(4, &log[..]);
+/// let found: Vec<StreamMatch> = StreamScanner::new(&ac, reader)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(found, [
+///     StreamMatch { start: 8, end: 12 },
+///     StreamMatch { start: 18, end: 23 },
+/// ]);
+/// ```
+pub struct StreamScanner<'a, R, S = RandomState> {
+    automaton: &'a AhoCorasick<u8, S>,
+    reader: R,
+    state: usize,
+    /// 已读入的字节数量
+    offset: u64,
+    /// 当前位置下一个要产生的匹配的终状态
+    output: Option<usize>,
+}
+
+impl<'a, R, S> StreamScanner<'a, R, S> {
+    /// new Self, scan from the current position of the reader,
+    /// the offsets start from zero
+    pub fn new(automaton: &'a AhoCorasick<u8, S>, reader: R) -> Self {
+        Self {
+            automaton,
+            reader,
+            state: 0,
+            offset: 0,
+            output: None,
+        }
+    }
+
+    /// return the number of bytes scanned
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// get the inner reader
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// into the inner reader, the unscanned buffered bytes are kept in it
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R, S> Iterator for StreamScanner<'_, R, S>
+where R: BufRead,
+      S: BuildHasher,
+{
+    type Item = io::Result<StreamMatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(output) = self.output {
+                let state = &self.automaton.states[output];
+                self.output = state.output;
+                let start = self.offset - state.depth as u64;
+                return Some(Ok(StreamMatch { start, end: self.offset }));
+            }
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Some(Err(e)),
+            };
+            if buf.is_empty() {
+                return None;
+            }
+            // 找到匹配时只消耗已扫描的部分, 下次调用从其后继续
+            let mut scanned = buf.len();
+            for (i, byte) in buf.iter().enumerate() {
+                self.state = self.automaton.next_state(self.state, byte);
+                self.offset += 1;
+                self.output = self.automaton.first_output(self.state);
+                if self.output.is_some() {
+                    scanned = i + 1;
+                    break;
+                }
+            }
+            self.reader.consume(scanned);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use core::{
+    fmt::{self, Debug},
+    hash::BuildHasher,
+};
+use alloc::{string::String, vec, vec::Vec};
+
+use crate::{
+    collections::RandomState,
+    trie_node::{ChildsIter, TrieNode},
+    TrieTree,
+};
+
+/// A TrieTree of `char`s that works with `&str` and `String` directly
+/// ===
+/// Iteration reuses one `String` buffer, see [`StringIter::next_str`]
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::StringTrie;
+/// let mut tree = StringTrie::new();
+/// assert!(tree.insert("apple"));
+/// assert!(tree.insert("apply"));
+/// assert!(tree.insert("bee"));
+/// assert!(tree.contains("apple"));
+/// assert!(! tree.contains("app"));
+/// assert!(tree.starts_with("app"));
+/// let mut words: Vec<String> = tree.completions("app").collect();
+/// words.sort();
+/// assert_eq!(words, ["apple", "apply"]);
+/// ```
+pub struct StringTrie<S = RandomState> {
+    tree: TrieTree<char, S>,
+}
+
+impl StringTrie {
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Default for StringTrie {
+    fn default() -> Self {
+        Self { tree: TrieTree::new() }
+    }
+}
+
+impl<S> StringTrie<S> {
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self { tree: TrieTree::with_hasher(hasher) }
+    }
+
+    /// return strings count
+    pub fn count(&self) -> usize {
+        self.tree.count()
+    }
+
+    /// get the inner tree of `char`s
+    pub fn as_tree(&self) -> &TrieTree<char, S> {
+        &self.tree
+    }
+
+    /// into the inner tree of `char`s
+    pub fn into_tree(self) -> TrieTree<char, S> {
+        self.tree
+    }
+
+    /// get iterator of all strings
+    pub fn iter(&self) -> StringIter<'_, S> {
+        StringIter::new(&self.tree.root, String::new())
+    }
+}
+
+impl<S> StringTrie<S>
+where S: BuildHasher,
+{
+    /// clear all strings
+    pub fn clear(&mut self)
+    where S: Clone
+    {
+        self.tree.clear();
+    }
+
+    /// insert a string
+    /// - return whether the insertion string was successful
+    pub fn insert(&mut self, s: &str) -> bool
+    where S: Clone
+    {
+        self.tree.insert(s.chars())
+    }
+
+    /// Whether the string is in the tree
+    pub fn contains(&self, s: &str) -> bool {
+        self.tree.query(s.chars())
+    }
+
+    /// Whether any string in the tree starts with the prefix
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::StringTrie;
+    /// let tree = StringTrie::from_iter(["abc"]);
+    /// assert!(tree.starts_with(""));
+    /// assert!(tree.starts_with("ab"));
+    /// assert!(tree.starts_with("abc"));
+    /// assert!(! tree.starts_with("abcd"));
+    /// ```
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.tree.query_nostop(prefix.chars()).is_some()
+    }
+
+    /// Get the Iterator of the strings starting with the prefix,
+    /// the yielded strings include the prefix<br/>
+    /// If there is no matching string, the iterator is empty
+    pub fn completions(&self, prefix: &str) -> StringIter<'_, S> {
+        match self.tree.root.query_node(prefix.chars()) {
+            Some(node) => StringIter::new(node, String::from(prefix)),
+            None => StringIter::empty(),
+        }
+    }
+
+    /// remove a string and clean up overhanging branches
+    pub fn remove(&mut self, s: &str) -> bool {
+        self.tree.remove(s.chars())
+    }
+
+    /// Shrink each `HashMap` in the tree
+    pub fn shrink_to_fit(&mut self) {
+        self.tree.shrink_to_fit();
+    }
+}
+
+impl<S> From<TrieTree<char, S>> for StringTrie<S> {
+    fn from(tree: TrieTree<char, S>) -> Self {
+        Self { tree }
+    }
+}
+
+impl<S> Clone for StringTrie<S>
+where S: BuildHasher + Clone,
+{
+    fn clone(&self) -> Self {
+        Self { tree: self.tree.clone() }
+    }
+}
+
+impl<S> Debug for StringTrie<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.tree.fmt(f)
+    }
+}
+
+impl<S: BuildHasher> PartialEq for StringTrie<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.tree == other.tree
+    }
+}
+
+impl<S: BuildHasher> Eq for StringTrie<S> {}
+
+impl<'s> FromIterator<&'s str> for StringTrie {
+    fn from_iter<I: IntoIterator<Item = &'s str>>(iter: I) -> Self {
+        let mut tree = Self::new();
+        tree.extend(iter);
+        tree
+    }
+}
+
+impl<'s, S> Extend<&'s str> for StringTrie<S>
+where S: BuildHasher + Clone,
+{
+    fn extend<I: IntoIterator<Item = &'s str>>(&mut self, iter: I) {
+        for s in iter {
+            self.insert(s);
+        }
+    }
+}
+
+impl<'a, S> IntoIterator for &'a StringTrie<S> {
+    type Item = String;
+    type IntoIter = StringIter<'a, S>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator used to iterate over the strings of `StringTrie`
+pub struct StringIter<'a, S = RandomState> {
+    /// 存储每个节点以及其是否为终节点
+    /// 当获取该值后将是否为终节点设置为否
+    nodes: Vec<(bool, ChildsIter<'a, char, S>)>,
+    /// 当前路径上的字符, 所有结果共用该缓冲区
+    buf: String,
+}
+
+impl<'a, S> StringIter<'a, S> {
+    fn new(node: &'a TrieNode<char, S>, prefix: String) -> Self {
+        Self {
+            nodes: vec![(node.is_stop(), node.childs().iter())],
+            buf: prefix,
+        }
+    }
+
+    fn empty() -> Self {
+        Self { nodes: Vec::new(), buf: String::new() }
+    }
+
+    /// Get the next string without allocating,
+    /// the reference is valid until the next call
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::StringTrie;
+    /// let tree = StringTrie::from_iter(["abc"]);
+    /// let mut iter = tree.iter();
+    /// assert_eq!(iter.next_str(), Some("abc"));
+    /// assert_eq!(iter.next_str(), None);
+    /// ```
+    pub fn next_str(&mut self) -> Option<&str> {
+        loop {
+            let (is_stop, kvs) = self.nodes.last_mut()?;
+            if *is_stop {
+                *is_stop = false;
+                break;
+            } else if let Some((&ch, node)) = kvs.next() {
+                // 在末端迭代到下一个分支
+                self.buf.push(ch);
+                self.nodes.push((node.is_stop(), node.childs().iter()));
+            } else {
+                // 末端迭代到了底部, 前缀部分不会被弹出
+                self.nodes.pop();
+                if ! self.nodes.is_empty() {
+                    self.buf.pop();
+                }
+            }
+        }
+        Some(&self.buf)
+    }
+}
+
+impl<S> Iterator for StringIter<'_, S> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_str().map(String::from)
+    }
+}
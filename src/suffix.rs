@@ -0,0 +1,306 @@
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasher, Hash},
+};
+use alloc::{vec, vec::Vec};
+
+use crate::collections::{HashMap, RandomState};
+
+/// 根节点的下标
+const ROOT: usize = 0;
+
+/// 压缩后缀树的节点, 入边的标签引用原序列的一个区间
+#[derive(Clone)]
+struct SuffixNode<T, S> {
+    /// 入边标签为`texts[doc][start..end]`
+    doc: usize,
+    start: usize,
+    end: usize,
+    /// 子节点们的下标, 键为子节点入边标签的首个元素
+    childs: HashMap<T, usize, S>,
+    /// 恰好在此节点结束的后缀, (文档, 偏移)
+    ends: Vec<(usize, usize)>,
+}
+
+impl<T, S> SuffixNode<T, S> {
+    fn with_hasher(hasher: S) -> Self {
+        Self {
+            doc: 0,
+            start: 0,
+            end: 0,
+            childs: HashMap::with_capacity_and_hasher(0, hasher),
+            ends: Vec::new(),
+        }
+    }
+}
+
+/// A generalized suffix index over multiple sequences
+/// ===
+/// All suffixes of each sequence are inserted into a compressed suffix tree,
+/// the edge labels reference ranges of the stored sequences,
+/// so the memory is linear in the total length of the sequences.
+/// Building takes `O(n²)` time in the worst case, where `n` is the length of a sequence.
+///
+/// # Examples
+/// ```
+/// # use fast_trie_tree::SuffixIndex;
+/// let index = SuffixIndex::build(["banana".chars(), "bandana".chars()]);
+/// assert!(index.contains_substring("nan".chars()));
+/// assert!(! index.contains_substring("nab".chars()));
+/// assert_eq!(index.occurrences("ana".chars()), [(0, 1), (0, 3), (1, 4)]);
+/// assert_eq!(index.longest_common_substring(0, 1), ['a', 'n', 'a']);
+/// ```
+#[derive(Clone)]
+pub struct SuffixIndex<T, S = RandomState> {
+    /// 所有文档的序列
+    texts: Vec<Vec<T>>,
+    /// 所有节点, 第零个为根节点
+    nodes: Vec<SuffixNode<T, S>>,
+}
+
+impl<T> SuffixIndex<T>
+where T: Hash + Eq
+{
+    /// new Self
+    /// - use Self::default()
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T> SuffixIndex<T>
+where T: Hash + Eq + Clone
+{
+    /// Build the index of the sequences, the document id of each sequence is
+    /// its index in `texts`
+    pub fn build<I>(texts: impl IntoIterator<Item = I>) -> Self
+    where I: IntoIterator<Item = T>
+    {
+        texts.into_iter().collect()
+    }
+}
+
+impl<T> Default for SuffixIndex<T>
+where T: Hash + Eq
+{
+    fn default() -> Self {
+        Self::with_hasher(RandomState::default())
+    }
+}
+
+impl<T, S> SuffixIndex<T, S> {
+    /// new Self, use the given hasher builder for every node
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            texts: Vec::new(),
+            nodes: vec![SuffixNode::with_hasher(hasher)],
+        }
+    }
+
+    /// return the number of documents
+    pub fn doc_count(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Get the sequence of the document
+    pub fn doc(&self, doc: usize) -> Option<&[T]> {
+        self.texts.get(doc).map(Vec::as_slice)
+    }
+
+    /// return the number of nodes, including the root
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// 节点入边的标签
+    fn label(&self, node: usize) -> &[T] {
+        let SuffixNode { doc, start, end, .. } = self.nodes[node];
+        &self.texts[doc][start..end]
+    }
+}
+
+impl<T, S> SuffixIndex<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
+{
+    /// 查询模式串末尾处或其所在边下方的节点
+    fn locate<Q>(&self, pattern: impl IntoIterator<Item = Q>) -> Option<usize>
+    where Q: Borrow<T>
+    {
+        let mut pattern = pattern.into_iter();
+        let mut node = ROOT;
+        while let Some(first) = pattern.next() {
+            node = *self.nodes[node].childs.get(first.borrow())?;
+            for elem in &self.label(node)[1..] {
+                match pattern.next() {
+                    Some(query) if query.borrow() == elem => (),
+                    Some(_) => return None,
+                    None => return Some(node),
+                }
+            }
+        }
+        Some(node)
+    }
+
+    /// insert a sequence as a new document
+    /// - return the document id
+    pub fn push(&mut self, text: impl IntoIterator<Item = T>) -> usize
+    where T: Clone,
+          S: Clone,
+    {
+        let doc = self.texts.len();
+        self.texts.push(text.into_iter().collect());
+        for offset in 0..self.texts[doc].len() {
+            self.insert_suffix(doc, offset);
+        }
+        doc
+    }
+
+    /// 插入文档的一个后缀, 必要时分裂边
+    fn insert_suffix(&mut self, doc: usize, offset: usize)
+    where T: Clone,
+          S: Clone,
+    {
+        let Self { texts, nodes } = self;
+        let text = &texts[doc];
+        let hasher = nodes[ROOT].childs.hasher().clone();
+        let mut node = ROOT;
+        let mut i = offset;
+        loop {
+            let Some(elem) = text.get(i) else {
+                nodes[node].ends.push((doc, offset));
+                return;
+            };
+            let Some(&child) = nodes[node].childs.get(elem) else {
+                let mut leaf = SuffixNode::with_hasher(hasher);
+                (leaf.doc, leaf.start, leaf.end) = (doc, i, text.len());
+                leaf.ends.push((doc, offset));
+                nodes.push(leaf);
+                let leaf = nodes.len() - 1;
+                nodes[node].childs.insert(elem.clone(), leaf);
+                return;
+            };
+            let SuffixNode { doc: label_doc, start, end, .. } = nodes[child];
+            let label = &texts[label_doc][start..end];
+            let matched = label.iter()
+                .zip(&text[i..])
+                .take_while(|(a, b)| a == b)
+                .count();
+            if matched < label.len() {
+                // 在不匹配处分裂边, 分裂出的节点继承原边标签的前半段
+                let mut mid = SuffixNode::with_hasher(hasher.clone());
+                (mid.doc, mid.start, mid.end) = (label_doc, start, start + matched);
+                mid.childs.insert(label[matched].clone(), child);
+                nodes[child].start = start + matched;
+                nodes.push(mid);
+                let mid = nodes.len() - 1;
+                nodes[node].childs.insert(elem.clone(), mid);
+                node = mid;
+            } else {
+                node = child;
+            }
+            i += matched;
+        }
+    }
+
+    /// Whether the pattern is a substring of any document
+    pub fn contains_substring<Q>(&self, pattern: impl IntoIterator<Item = Q>) -> bool
+    where Q: Borrow<T>
+    {
+        self.locate(pattern).is_some()
+    }
+
+    /// Get all occurrences of the pattern, as sorted `(doc, offset)` pairs
+    /// # Examples
+    /// ```
+    /// # use fast_trie_tree::SuffixIndex;
+    /// let index = SuffixIndex::build([[1, 2, 1, 2], [2, 1, 3, 1]]);
+    /// assert_eq!(index.occurrences([2, 1]), [(0, 1), (1, 0)]);
+    /// assert_eq!(index.occurrences([1]), [(0, 0), (0, 2), (1, 1), (1, 3)]);
+    /// assert!(index.occurrences([3, 2]).is_empty());
+    /// ```
+    pub fn occurrences<Q>(&self, pattern: impl IntoIterator<Item = Q>) -> Vec<(usize, usize)>
+    where Q: Borrow<T>
+    {
+        let Some(node) = self.locate(pattern) else {
+            return Vec::new();
+        };
+        let mut res = Vec::new();
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            res.extend_from_slice(&node.ends);
+            stack.extend(node.childs.values());
+        }
+        res.sort_unstable();
+        res
+    }
+
+    /// Get the longest common substring of the documents `a` and `b`,
+    /// referencing the document `a`<br/>
+    /// If there are multiple longest common substrings, one of them is returned
+    ///
+    /// # Panics
+    /// Panics if `a` or `b` is not a document id
+    pub fn longest_common_substring(&self, a: usize, b: usize) -> &[T] {
+        assert!(a < self.texts.len() && b < self.texts.len(), "document id out of range");
+        // 广度优先得到节点顺序, 逆序处理即可保证子节点先于父节点
+        // (节点, 父节点在顺序中的位置, 字符串深度)
+        let mut order = vec![(ROOT, None, 0)];
+        let mut i = 0;
+        while let Some(&(node, _, depth)) = order.get(i) {
+            for &child in self.nodes[node].childs.values() {
+                order.push((child, Some(i), depth + self.label(child).len()));
+            }
+            i += 1;
+        }
+        // 子树中是否含有文档a, b的后缀, 以及文档a的一个后缀偏移
+        let mut states = vec![(false, false, 0); order.len()];
+        let (mut best_offset, mut best_len) = (0, 0);
+        for (pos, &(node, parent, depth)) in order.iter().enumerate().rev() {
+            let mut state = states[pos];
+            for &(doc, offset) in &self.nodes[node].ends {
+                if doc == a {
+                    state.0 = true;
+                    state.2 = offset;
+                }
+                state.1 |= doc == b;
+            }
+            if state.0 && state.1 && depth > best_len {
+                (best_offset, best_len) = (state.2, depth);
+            }
+            if let Some(parent) = parent {
+                let parent = &mut states[parent];
+                if state.0 {
+                    parent.0 = true;
+                    parent.2 = state.2;
+                }
+                parent.1 |= state.1;
+            }
+        }
+        &self.texts[a][best_offset..best_offset+best_len]
+    }
+}
+
+impl<T, I> FromIterator<I> for SuffixIndex<T>
+where T: Hash + Eq + Clone,
+      I: IntoIterator<Item = T>,
+{
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        let mut index = Self::new();
+        index.extend(iter);
+        index
+    }
+}
+
+impl<T, S, I> Extend<I> for SuffixIndex<T, S>
+where T: Hash + Eq + Clone,
+      S: BuildHasher + Clone,
+      I: IntoIterator<Item = T>,
+{
+    fn extend<It: IntoIterator<Item = I>>(&mut self, iter: It) {
+        for text in iter {
+            self.push(text);
+        }
+    }
+}
@@ -1,5 +1,11 @@
 use super::*;
 
+// 未启用`std`时测试仍会链接标准库, 在此引入其预导入部分
+#[cfg(not(feature = "std"))]
+extern crate std;
+#[cfg(not(feature = "std"))]
+use std::{prelude::rust_2021::*, vec, format};
+
 const TEST_NUMS: [&[i32]; 59] = {
     [
         &[69, 30, 63, 30, 59, 75, 13, 79, 39],
@@ -105,6 +111,7 @@ fn empty_test() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn insert_test() {
     let mut tree = TrieTree::new();
     let strs = ["a", "abc", "ab", "def", "f", ""];
@@ -126,6 +133,7 @@ fn insert_test() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison, clippy::into_iter_on_ref)]
 fn other_type_test() {
     let arr: &[&[i32]] = &TEST_NUMS;
     let mut tree = TrieTree::new();
@@ -148,6 +156,7 @@ fn other_type_test() {
 }
 
 #[test]
+#[allow(clippy::into_iter_on_ref, clippy::redundant_slicing)]
 fn iter_test() {
     let mut tree = TrieTree::new();
     for nums in TEST_NUMS {
@@ -273,3 +282,1126 @@ fn test_ref_into_iter() {
         }
     }
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn concurrent_test() {
+    let tree = ConcurrentTrie::with_shards(4);
+    std::thread::scope(|s| {
+        for chunk in TEST_NUMS.chunks(8) {
+            let tree = &tree;
+            s.spawn(move || {
+                for &nums in chunk {
+                    assert!(tree.insert(nums.iter().copied()));
+                }
+            });
+        }
+    });
+    assert_eq!(tree.count(), TEST_NUMS.len());
+    assert_eq!(tree.snapshot(), TrieTree::from_iter(TEST_NUMS.map(|x| x.iter().copied())));
+
+    std::thread::scope(|s| {
+        for chunk in TEST_NUMS.chunks(8) {
+            let tree = &tree;
+            s.spawn(move || {
+                for nums in chunk {
+                    assert!(tree.query(*nums));
+                    assert!(tree.remove(*nums));
+                    assert!(! tree.query(*nums));
+                }
+            });
+        }
+    });
+    assert_eq!(tree.count(), 0);
+    assert_eq!(tree.iter().len(), 0);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_test() {
+    use rayon::prelude::*;
+
+    let tree: TrieTree<_> = TEST_NUMS.into_par_iter()
+        .map(|nums| nums.iter().copied())
+        .collect();
+    let origin = TrieTree::from_iter(TEST_NUMS.map(|x| x.iter().copied()));
+    assert_eq!(tree.count(), TEST_NUMS.len());
+    assert_eq!(tree, origin);
+
+    let mut tree = TrieTree::from_iter(TEST_NUMS[..20].iter().map(|x| x.iter().copied()));
+    tree.par_extend(TEST_NUMS.into_par_iter().map(|nums| nums.iter().copied()));
+    assert_eq!(tree.count(), TEST_NUMS.len());
+    assert_eq!(tree, origin);
+
+    let mut tree = TrieTree::new();
+    tree.par_extend(TEST_NUMS.into_par_iter().chain(TEST_NUMS).map(|nums| nums.iter().copied()));
+    assert_eq!(tree.count(), TEST_NUMS.len());
+    assert_eq!(tree, origin);
+
+    let mut vals: Vec<_> = tree.par_iter().collect();
+    let mut origin_vals: Vec<_> = tree.iter().collect();
+    vals.sort();
+    origin_vals.sort();
+    assert_eq!(vals, origin_vals);
+}
+
+#[test]
+fn debug_compact_test() {
+    let tree = TrieTree::from_iter(["ab".chars(), "a".chars()]);
+    assert_eq!(format!("{:?}", tree),
+               "TrieTree { count: 2, root: (/):{['a']:{['b']:{}}} }");
+}
+
+#[test]
+fn deep_key_test() {
+    const DEPTH: usize = 1_000_000;
+
+    let key = || (0..DEPTH).map(|i| i % 3);
+    let mut tree = TrieTree::new();
+    assert!(tree.insert(key()));
+    assert!(tree.insert(key().take(DEPTH / 2)));
+    assert!(tree.query(key()));
+    assert_eq!(tree.iter().map(|values| values.len()).max(), Some(DEPTH));
+
+    let cloned = tree.clone();
+    assert_eq!(cloned, tree);
+    assert!(format!("{:?}", cloned).len() > DEPTH);
+    tree.shrink_to_fit();
+    let root: TrieNode<_> = cloned.into();
+    assert_eq!(TrieTree::from(root).count(), 2);
+
+    assert!(tree.remove(key()));
+    assert!(! tree.query(key()));
+    assert!(tree.query(key().take(DEPTH / 2)));
+    assert_eq!(tree.query_nostop(key().take(DEPTH / 2 + 1)), None);
+    assert!(tree.insert(key()));
+    drop(tree);
+}
+
+#[test]
+fn hasher_test() {
+    use std::{collections::hash_map::DefaultHasher, hash::{BuildHasher, Hasher}};
+
+    /// 没有实现`Default`的固定种子哈希构建器
+    #[derive(Clone)]
+    struct FixedState(u64);
+    impl BuildHasher for FixedState {
+        type Hasher = DefaultHasher;
+
+        fn build_hasher(&self) -> Self::Hasher {
+            let mut hasher = DefaultHasher::new();
+            hasher.write_u64(self.0);
+            hasher
+        }
+    }
+
+    let mut tree = TrieTree::with_hasher(FixedState(42));
+    for nums in TEST_NUMS {
+        assert!(tree.insert(nums.iter().copied()));
+    }
+    for nums in TEST_NUMS {
+        assert!(tree.query(nums));
+    }
+    assert_eq!(tree.count(), TEST_NUMS.len());
+    let mut nodes = 0;
+    tree.clone().root.map_nodes_first_root(&mut |node| {
+        assert_eq!(node.childs().hasher().0, 42);
+        nodes += 1;
+    });
+    assert!(nodes > TEST_NUMS.len());
+    tree.clear();
+    assert!(tree.insert([1, 2]));
+    assert_eq!(tree.root.get_child(1).unwrap().childs().hasher().0, 42);
+}
+
+#[test]
+fn arena_test() {
+    let mut tree = ArenaTrieTree::new();
+    for (i, nums) in TEST_NUMS.into_iter().enumerate() {
+        assert_eq!(tree.count(), i);
+        assert!(tree.insert(nums.iter().copied()));
+    }
+    let node_count = tree.node_count();
+    let mut vals: Vec<_> = tree.iter().collect();
+    let origin = TrieTree::from_iter(TEST_NUMS.map(|x| x.iter().copied()));
+    let mut origin_vals: Vec<_> = origin.iter().collect();
+    vals.sort();
+    origin_vals.sort();
+    assert_eq!(vals, origin_vals);
+
+    for nums in TEST_NUMS {
+        assert!(tree.query(nums));
+        assert!(tree.remove(nums));
+        assert!(! tree.query(nums));
+    }
+    assert_eq!(tree.count(), 0);
+    assert_eq!(tree.node_count(), 1);
+    assert_eq!(tree.iter().count(), 0);
+
+    for nums in TEST_NUMS {
+        assert!(tree.insert(nums.iter().copied()));
+    }
+    assert_eq!(tree.node_count(), node_count);
+
+    // 删除后再插入复用空闲节点, 竞技场不再增长
+    let capacity = tree.capacity();
+    for _ in 0..4 {
+        for &nums in TEST_NUMS.iter().step_by(2) {
+            assert!(tree.remove(nums));
+        }
+        for nums in TEST_NUMS.iter().step_by(2) {
+            assert!(tree.insert(nums.iter().copied()));
+        }
+        for nums in TEST_NUMS {
+            assert!(tree.remove(nums));
+        }
+        for nums in TEST_NUMS {
+            assert!(tree.insert(nums.iter().copied()));
+        }
+        assert_eq!(tree.node_count(), node_count);
+        assert_eq!(tree.capacity(), capacity);
+    }
+}
+
+#[test]
+fn stats_test() {
+    let mut tree = TrieTree::from_iter(TEST_NUMS);
+    let stats = tree.stats();
+    assert_eq!(stats.stop_count, tree.count());
+    assert_eq!(stats.max_depth, 9);
+    assert_eq!(stats.node_count, stats.childs_len + 1);
+    assert_eq!(stats.branching.iter().sum::<usize>(), stats.node_count);
+    assert!(stats.single_child_chains <= stats.single_child_nodes);
+    assert_eq!(TrieTree::<i32>::new().stats().avg_depth, 0.0);
+
+    for nums in &TEST_NUMS[..40] {
+        tree.remove(*nums);
+    }
+    let before = tree.stats();
+    tree.shrink_to_fit();
+    let after = tree.stats();
+    assert_eq!(before.childs_len, after.childs_len);
+    assert!(after.childs_capacity < before.childs_capacity);
+    assert!(after.heap_bytes < before.heap_bytes);
+    assert!(after.unused_capacity_ratio() < before.unused_capacity_ratio());
+}
+
+#[test]
+fn dot_test() {
+    let tree = TrieTree::from_iter(TEST_NUMS);
+    let stats = tree.stats();
+    let mut dot = String::new();
+    tree.to_dot(&mut dot).unwrap();
+    assert!(dot.starts_with("digraph TrieTree {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(dot.matches(" -> ").count(), stats.node_count - 1);
+    assert_eq!(dot.matches("shape=box").count(), stats.stop_count);
+
+    dot.clear();
+    tree.to_dot_with(&mut dot, &DotOptions::new().max_nodes(10)).unwrap();
+    assert_eq!(dot.matches("[label=\"(").count()
+               + dot.matches("[label=\"[").count(), 10);
+    assert!(dot.contains("more\", shape=plaintext"));
+
+    let tree = TrieTree::from_iter(["a\"\\".chars()]);
+    dot.clear();
+    tree.to_dot(&mut dot).unwrap();
+    assert!(dot.contains(r#"label="['\\\\']""#), "{dot}");
+    assert!(dot.contains(r#"label="('\"')""#), "{dot}");
+
+    #[cfg(feature = "std")]
+    {
+        let tree = TrieTree::from_iter(TEST_NUMS);
+        let mut dot = String::new();
+        tree.to_dot_with(&mut dot, &DotOptions::new()).unwrap();
+        let mut bytes = Vec::new();
+        tree.to_dot_io(&mut bytes, &DotOptions::new()).unwrap();
+        assert_eq!(bytes, dot.as_bytes());
+
+        let mut full = [0; 16];
+        let err = tree.to_dot_io(&mut &mut full[..], &DotOptions::new()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    }
+}
+
+#[test]
+fn display_test() {
+    let [tree1, tree2] = [
+        TrieTree::from_iter(TEST_NUMS),
+        TrieTree::from_iter(TEST_NUMS.into_iter().rev())];
+    for style in [DisplayStyle::Nested, DisplayStyle::Tree] {
+        let display = |tree: &TrieTree<_>| tree.display()
+            .sorted()
+            .style(style)
+            .max_depth(4)
+            .max_children(6)
+            .to_string();
+        assert_eq!(display(&tree1), display(&tree2));
+        assert!(display(&tree1).contains("\u{2026} "));
+    }
+
+    let tree = TrieTree::from_iter(["abc".chars(), "a".chars()]);
+    assert_eq!(tree.display().to_string(), format!("{:#?}", tree.root));
+    // 兄弟节点之间的分隔符与`Debug`相同, 未排序时子节点顺序也相同
+    assert_eq!(tree1.display().to_string(), format!("{:#?}", tree1.root));
+    assert_eq!(TrieTree::<char>::new().display().to_string(), "(/): {}");
+    assert_eq!(TrieTree::<char>::new().display().style(DisplayStyle::Tree).to_string(), "(/)\n");
+}
+
+#[test]
+fn parse_test() {
+    let tree = TrieTree::from_iter(TEST_NUMS);
+    let nums = tree.iter().map(|x| x.into_iter().copied().copied().collect::<Vec<i32>>());
+    let tree = TrieTree::from_iter(nums);
+    for text in [format!("{:?}", tree), format!("{:#?}", tree), format!("{:#?}", tree.root)] {
+        let parsed: TrieTree<i32> = text.parse().unwrap();
+        assert_eq!(parsed, tree);
+        assert_eq!(parsed.count(), tree.count());
+    }
+
+    let strs = TrieTree::from_iter([
+        ["a\"b".to_string(), "\\".into()],
+        ["\n\u{7f}".into(), "'x'".into()],
+        ["".into(), "(1, [2])".into()],
+    ]);
+    assert_eq!(format!("{:#?}", strs).parse::<TrieTree<String>>().unwrap(), strs);
+    let chars = TrieTree::from_iter(["'\"\\\t".chars(), "\u{10ffff}".chars()]);
+    assert_eq!(format!("{:?}", chars).parse::<TrieTree<char>>().unwrap(), chars);
+
+    let err = |s: &str| {
+        let err = s.parse::<TrieTree<char>>().unwrap_err();
+        (err.line, err.column, err.kind)
+    };
+    assert_eq!(err("['a']: {}"), (1, 2, ParseErrorKind::InvalidRoot));
+    assert_eq!(err("(/): {\n  ('a'): {}\n}"), (2, 4, ParseErrorKind::DanglingNode));
+    assert_eq!(err("(/): {['a']: {}, ['a']: {}}"), (1, 19, ParseErrorKind::DuplicateChild));
+    assert_eq!(err("(/): {['a']: {} ['b']: {}}"),
+               (1, 17, ParseErrorKind::Unexpected { found: Some('['), expected: "`,` or `}`" }));
+    assert_eq!(err("(/): {['a']: {}"),
+               (1, 16, ParseErrorKind::Unexpected { found: None, expected: "`,` or `}`" }));
+    assert_eq!(err("(/): {['\\q']: {}}"), (1, 10, ParseErrorKind::InvalidEscape));
+    assert_eq!(err("TrieTree { count: 2, root: (/): {['a']: {}} }"),
+               (1, 19, ParseErrorKind::CountMismatch { expected: 2, found: 1 }));
+    assert_eq!(err("(/): {} x"),
+               (1, 9, ParseErrorKind::Unexpected { found: Some('x'), expected: "end of input" }));
+    assert!(! "(/): {x}".parse::<TrieTree<char>>().unwrap_err().to_string().is_empty());
+    assert!(matches!(err("(/): {[ab]: {}}"), (1, 8, ParseErrorKind::InvalidElement(_))));
+    assert_eq!("(/): {['a']: {},}".parse::<TrieTree<char>>().unwrap().count(), 1);
+}
+
+#[test]
+fn hash_ord_test() {
+    use std::cmp::Ordering;
+    use std::collections::{BTreeSet, HashSet, hash_map::DefaultHasher};
+    use std::hash::Hasher;
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let a = TrieTree::from_iter(TEST_NUMS);
+    let b = TrieTree::from_iter(TEST_NUMS.into_iter().rev());
+    let mut c = TrieTree::from_iter(TEST_NUMS);
+    assert_eq!(a, b);
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert_eq!(a.cmp(&b), Ordering::Equal);
+    assert!(c.remove(TEST_NUMS[0]));
+    assert_ne!(hash_of(&a), hash_of(&c));
+    assert_ne!(a.cmp(&c), Ordering::Equal);
+
+    // 相同的值但有悬垂分支
+    let mut d = TrieTree::from_iter(["ab".chars(), "x".chars()]);
+    assert!(unsafe { d.remove_no_clean("ab".chars()) });
+    let e = TrieTree::from_iter(["x".chars()]);
+    assert_ne!(d, e);
+    assert_ne!(d.cmp(&e), Ordering::Equal);
+    assert_eq!(d.cmp(&e), e.cmp(&d).reverse());
+
+    let trees = [
+        TrieTree::from_iter(["ab".chars(), "c".chars()]),
+        TrieTree::from_iter(["c".chars(), "ab".chars()]),
+        TrieTree::from_iter(["a".chars()]),
+        TrieTree::from_iter(["".chars()]),
+        TrieTree::new(),
+        TrieTree::from_iter(["ab".chars()]),
+    ];
+    assert_eq!(HashSet::<_>::from_iter(trees.iter()).len(), 5);
+    let sorted: Vec<_> = BTreeSet::from_iter(trees.iter())
+        .into_iter()
+        .map(|tree| tree.count())
+        .collect();
+    assert_eq!(sorted, [0, 1, 1, 1, 2]);
+    let mut sorted = trees.to_vec();
+    sorted.sort();
+    assert_eq!(sorted[0], TrieTree::new());
+    assert_eq!(sorted[1], TrieTree::from_iter(["".chars()]));
+    assert_eq!(sorted[2], TrieTree::from_iter(["a".chars()]));
+    assert_eq!(sorted[3], TrieTree::from_iter(["ab".chars()]));
+}
+
+#[test]
+fn key_test() {
+    let mut strs = KeyTrie::<str>::from_iter(["abc", "abd", "b", ""]);
+    assert_eq!(strs.count(), 4);
+    assert!(strs.query(""));
+    assert_eq!(strs.query_nostop("ab"), Some(false));
+    assert_eq!(strs.query_nostop("x"), None);
+    let mut keys: Vec<String> = strs.iter().collect();
+    keys.sort();
+    assert_eq!(keys, ["", "abc", "abd", "b"]);
+    let mut keys: Vec<String> = strs.query_iter("a").unwrap().collect();
+    keys.sort();
+    assert_eq!(keys, ["abc", "abd"]);
+    assert!(strs.remove("abc"));
+    assert!(! strs.remove("abc"));
+    assert_eq!(strs.as_tree().query_iter("ab".chars()).unwrap().count(), 1);
+
+    let bytes = KeyTrie::<[u8]>::from_iter([&b"\xffa"[..], b"\xff"]);
+    let mut keys: Vec<Vec<u8>> = bytes.iter().collect();
+    keys.sort();
+    assert_eq!(keys, [&b"\xff"[..], b"\xffa"]);
+
+    #[cfg(feature = "std")]
+    {
+        use std::path::{Path, PathBuf};
+
+        let paths = KeyTrie::<Path>::from_iter([
+            Path::new("/usr/bin"),
+            Path::new("/usr//lib/"),
+            Path::new("src/lib.rs"),
+        ]);
+        assert!(paths.query(Path::new("/usr/lib")));
+        assert!(! paths.query(Path::new("usr/lib")));
+        let mut keys: Vec<PathBuf> = paths.query_iter(Path::new("/usr")).unwrap().collect();
+        keys.sort();
+        assert_eq!(keys, [Path::new("/usr/bin"), Path::new("/usr/lib")]);
+    }
+
+    let nums = KeyTrie::<i32>::from_iter([&-1, &0, &1, &i32::MAX, &i32::MIN]);
+    let mut keys: Vec<i32> = nums.iter().collect();
+    keys.sort();
+    assert_eq!(keys, [i32::MIN, -1, 0, 1, i32::MAX]);
+    let keys: Vec<u64> = KeyTrie::<u64>::from_iter([&0x0102_0304])
+        .query_iter(&0x0102_0304)
+        .unwrap()
+        .collect();
+    assert_eq!(keys, [0x0102_0304]);
+}
+
+#[test]
+fn string_trie_test() {
+    let mut tree = StringTrie::from_iter(["", "测试", "测量", "a", "ab", "b"]);
+    assert_eq!(tree.count(), 6);
+    assert!(tree.contains(""));
+    assert!(tree.contains("测试"));
+    assert!(! tree.contains("测"));
+    assert!(tree.starts_with("测"));
+    assert!(! tree.starts_with("x"));
+
+    let mut words: Vec<String> = tree.iter().collect();
+    words.sort();
+    assert_eq!(words, ["", "a", "ab", "b", "测试", "测量"]);
+    let mut words: Vec<String> = tree.completions("测").collect();
+    words.sort();
+    assert_eq!(words, ["测试", "测量"]);
+    assert_eq!(tree.completions("ab").collect::<Vec<_>>(), ["ab"]);
+    assert_eq!(tree.completions("x").count(), 0);
+
+    let mut iter = tree.completions("a");
+    let mut words = Vec::new();
+    while let Some(word) = iter.next_str() {
+        words.push(word.to_string());
+    }
+    words.sort();
+    assert_eq!(words, ["a", "ab"]);
+
+    assert!(tree.remove("测试"));
+    assert!(! tree.remove("测试"));
+    assert!(tree.starts_with("测"));
+    assert!(tree.remove("测量"));
+    assert!(! tree.starts_with("测"));
+    assert_eq!(tree.as_tree().count(), 4);
+    assert_eq!(tree, StringTrie::from_iter(["", "a", "ab", "b"]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn path_trie_test() {
+    use std::{fs, path::{Path, PathBuf}};
+
+    let mut paths = PathTrie::from_iter(["/a/b/c.txt", "/a/b", "/a/d/e.txt", "x/y"]);
+    assert_eq!(paths.count(), 4);
+    assert!(paths.contains("/a/b/"));
+    assert!(! paths.contains("/a"));
+    assert_eq!(paths.nearest_ancestor("/a/b/c.txt"), Some(PathBuf::from("/a/b/c.txt")));
+    assert_eq!(paths.nearest_ancestor("/a/b/z/w"), Some(PathBuf::from("/a/b")));
+    assert_eq!(paths.nearest_ancestor("/a/d"), None);
+    assert_eq!(paths.nearest_ancestor("x"), None);
+    let mut files: Vec<PathBuf> = paths.files_under("/a").collect();
+    files.sort();
+    assert_eq!(files, [Path::new("/a/b"), Path::new("/a/b/c.txt"), Path::new("/a/d/e.txt")]);
+    assert_eq!(paths.files_under("/z").count(), 0);
+    let mut names: Vec<_> = paths.children("/a").unwrap().collect();
+    names.sort();
+    assert_eq!(names, ["b", "d"]);
+    assert_eq!(paths.children("/a/b/c.txt").unwrap().count(), 0);
+    assert!(paths.remove("/a/d/e.txt"));
+    assert!(paths.children("/a/d").is_none());
+
+    let root = std::env::temp_dir().join(format!("fast_trie_tree_walk_{}", std::process::id()));
+    fs::create_dir_all(root.join("sub/deep")).unwrap();
+    fs::write(root.join("top.txt"), "").unwrap();
+    fs::write(root.join("sub/a.txt"), "").unwrap();
+    fs::write(root.join("sub/deep/b.txt"), "").unwrap();
+    let mut walked = PathTrie::new();
+    let res = walked.walk_dir(&root);
+    let again = walked.walk_dir(&root);
+
+    // 不可读的子目录被跳过, 其余路径仍被插入
+    #[cfg(unix)]
+    let locked = {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::create_dir(root.join("locked")).unwrap();
+        fs::write(root.join("locked/hidden.txt"), "").unwrap();
+        fs::set_permissions(root.join("locked"), fs::Permissions::from_mode(0o000)).unwrap();
+        // 特权用户仍然可以读取, 此时没有错误
+        let readable = fs::read_dir(root.join("locked")).is_ok();
+        let mut locked = PathTrie::new();
+        let res = locked.walk_dir(&root);
+        fs::set_permissions(root.join("locked"), fs::Permissions::from_mode(0o755)).unwrap();
+        (readable, res)
+    };
+    fs::remove_dir_all(&root).unwrap();
+    assert_eq!((res.0, res.1.len()), (3, 0));
+    assert_eq!((again.0, again.1.len()), (0, 0));
+    assert!(walked.contains(root.join("sub/deep/b.txt")));
+    assert!(! walked.contains(root.join("sub")));
+    assert_eq!(walked.files_under(root.join("sub")).count(), 2);
+    #[cfg(unix)]
+    match locked {
+        (true, (inserted, errors)) => assert_eq!((inserted, errors.len()), (4, 0)),
+        (false, (inserted, errors)) => {
+            assert_eq!(inserted, 3);
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].kind(), std::io::ErrorKind::PermissionDenied);
+        },
+    }
+
+    let (inserted, errors) = walked.walk_dir(&root);
+    assert_eq!(inserted, 0);
+    assert_eq!(errors[0].kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn router_test() {
+    let mut router = Router::new();
+    for (i, route) in [
+        "/",
+        "/a/b/c",
+        "/a/:x/d",
+        "/a/:x/*rest",
+        "/a/*all",
+        "/files/:dir/:name",
+    ].into_iter().enumerate() {
+        router.insert(route, i).unwrap();
+    }
+    assert_eq!(router.count(), 6);
+
+    let at = |path| router.at(path).map(|m| (*m.value, m.params));
+    assert_eq!(at(""), Some((0, vec![])));
+    assert_eq!(at("//"), Some((0, vec![])));
+    assert_eq!(at("/a/b/c"), Some((1, vec![])));
+    // 静态段匹配失败后回溯到参数段
+    assert_eq!(at("/a/b/d"), Some((2, vec![("x", "b")])));
+    assert_eq!(at("/a/b/e/f"), Some((3, vec![("x", "b"), ("rest", "e/f")])));
+    assert_eq!(at("//a/b//e/f/"), Some((3, vec![("x", "b"), ("rest", "e/f/")])));
+    assert_eq!(at("/a/b"), Some((4, vec![("all", "b")])));
+    assert_eq!(at("/a"), None);
+    assert_eq!(at("/files/src/lib.rs"), Some((5, vec![("dir", "src"), ("name", "lib.rs")])));
+    assert_eq!(at("/files/src"), None);
+
+    assert_eq!(router.insert("/a/b/c/", 9), Err(RouteError::Conflict {
+        route: "/a/b/c/".into(),
+        existing: "/a/b/c".into(),
+    }));
+    assert_eq!(router.insert("/a/:y", 9), Err(RouteError::Conflict {
+        route: "/a/:y".into(),
+        existing: "/a/:x/d".into(),
+    }));
+    assert_eq!(router.insert("/a/*other", 9), Err(RouteError::Conflict {
+        route: "/a/*other".into(),
+        existing: "/a/*all".into(),
+    }));
+    assert_eq!(router.insert("/a/*x/b", 9), Err(RouteError::InvalidSegment {
+        route: "/a/*x/b".into(),
+        segment: "*x".into(),
+    }));
+    assert_eq!(router.insert("/:", 9), Err(RouteError::InvalidSegment {
+        route: "/:".into(),
+        segment: ":".into(),
+    }));
+    assert_eq!(router.insert("/:a/:a", 9), Err(RouteError::DuplicateParam {
+        route: "/:a/:a".into(),
+        name: "a".into(),
+    }));
+    assert_eq!(router.count(), 6);
+    assert!(router.at("/a/:y").is_some_and(|m| *m.value == 4));
+
+    let mut routes: Vec<_> = router.iter().map(|(route, &i)| (i, route)).collect();
+    routes.sort();
+    assert_eq!(routes[3], (3, "/a/:x/*rest"));
+    assert_eq!(routes.len(), 6);
+
+    assert_eq!(router.remove("/a/:y/d"), Some(2));
+    assert_eq!(router.remove("/a/:x/d"), None);
+    assert_eq!(router.remove("/a/:x/*rest"), Some(3));
+    assert_eq!(at_value(&router, "/a/b/d"), Some(4));
+    // 参数节点已被清理, 可以使用新的参数名
+    router.insert("/a/:y", 7).unwrap();
+    assert_eq!(router.remove("/files/:d/:n"), Some(5));
+    router.insert("/files/:path", 8).unwrap();
+    assert_eq!(router.remove("/files/:path"), Some(8));
+    assert_eq!(router.remove("/a/*all"), Some(4));
+    assert_eq!(router.remove("/"), Some(0));
+    assert_eq!(router.count(), 2);
+    assert_eq!(at_value(&router, "/a/z"), Some(7));
+    assert_eq!(at_value(&router, "/"), None);
+
+    fn at_value<V: Copy>(router: &Router<V>, path: &str) -> Option<V> {
+        router.at(path).map(|m| *m.value)
+    }
+}
+
+#[test]
+fn ip_prefix_test() {
+    use std::net::IpAddr;
+
+    let prefix = |s: &str| s.parse::<IpPrefix>().unwrap();
+    let addr = |s: &str| s.parse::<IpAddr>().unwrap();
+    assert_eq!("10.0.0.0/33".parse::<IpPrefix>(), Err(InvalidIpPrefix));
+    assert_eq!("10.0.0.0/+8".parse::<IpPrefix>(), Err(InvalidIpPrefix));
+    assert_eq!("10.0.0/8".parse::<IpPrefix>(), Err(InvalidIpPrefix));
+    assert_eq!(prefix("2001:db8::1/32").to_string(), "2001:db8::/32");
+    assert_eq!(prefix("1.2.3.4").to_string(), "1.2.3.4/32");
+
+    let mut table = IpPrefixTrie::new();
+    assert_eq!(table.insert(prefix("10.0.0.0/8"), 1), None);
+    assert_eq!(table.insert(prefix("10.0.0.0/8"), 2), Some(1));
+    table.insert(prefix("10.128.0.0/9"), 3);
+    table.insert(prefix("10.128.0.1"), 4);
+    table.insert(prefix("::/0"), 5);
+    table.insert(prefix("2001:db8::/32"), 6);
+    assert_eq!(table.count(), 5);
+    assert_eq!(table.get(prefix("10.0.0.0/8")), Some(&2));
+    assert_eq!(table.get(prefix("10.0.0.0/9")), None);
+    *table.get_mut(prefix("::/0")).unwrap() += 10;
+
+    let longest = |table: &IpPrefixTrie<i32>, s| {
+        table.longest_match(addr(s)).map(|(p, &v)| (p.to_string(), v))
+    };
+    assert_eq!(longest(&table, "10.128.0.1"), Some(("10.128.0.1/32".into(), 4)));
+    assert_eq!(longest(&table, "10.128.0.2"), Some(("10.128.0.0/9".into(), 3)));
+    assert_eq!(longest(&table, "10.1.0.0"), Some(("10.0.0.0/8".into(), 2)));
+    assert_eq!(longest(&table, "11.0.0.0"), None);
+    assert_eq!(longest(&table, "2001:db8::1"), Some(("2001:db8::/32".into(), 6)));
+    assert_eq!(longest(&table, "2001:db9::1"), Some(("::/0".into(), 15)));
+    // IPv4与IPv6不混淆
+    assert_eq!(longest(&table, "::a00:1"), Some(("::/0".into(), 15)));
+
+    let values: Vec<_> = table.iter().map(|(_, &v)| v).collect();
+    assert_eq!(values, [2, 3, 4, 15, 6]);
+    let covering: Vec<_> = table.covering(prefix("10.128.0.1")).map(|(_, &v)| v).collect();
+    assert_eq!(covering, [2, 3, 4]);
+    let covered: Vec<_> = table.covered(prefix("10.0.0.0/8")).map(|(_, &v)| v).collect();
+    assert_eq!(covered, [2, 3, 4]);
+    assert_eq!(table.covered(prefix("12.0.0.0/8")).count(), 0);
+
+    assert_eq!(table.remove(prefix("10.128.0.0/9")), Some(3));
+    assert_eq!(table.remove(prefix("10.128.0.0/9")), None);
+    assert_eq!(longest(&table, "10.128.0.2"), Some(("10.0.0.0/8".into(), 2)));
+    assert_eq!(table.remove(prefix("10.128.0.1")), Some(4));
+    assert_eq!(table.remove(prefix("10.0.0.0/8")), Some(2));
+    assert_eq!(table.count(), 2);
+    assert!(table.covered(prefix("0.0.0.0/0")).next().is_none());
+
+    // 聚合后任意地址的最长匹配结果不变
+    let mut table = IpPrefixTrie::from_iter([
+        (prefix("192.168.0.0/24"), 'a'),
+        (prefix("192.168.1.0/24"), 'a'),
+        (prefix("192.168.2.0/24"), 'a'),
+        (prefix("192.168.3.0/24"), 'a'),
+        (prefix("192.168.3.128/25"), 'a'),
+        (prefix("192.168.4.0/24"), 'b'),
+        (prefix("0.0.0.0/1"), 'c'),
+        (prefix("128.0.0.0/1"), 'c'),
+    ]);
+    let probes = ["192.168.0.1", "192.168.3.200", "192.168.4.1", "192.168.5.1", "1.1.1.1"];
+    let before: Vec<_> = probes.iter().map(|s| table.longest_match(addr(s)).map(|x| *x.1)).collect();
+    assert_eq!(table.aggregate(), 5);
+    let after: Vec<_> = probes.iter().map(|s| table.longest_match(addr(s)).map(|x| *x.1)).collect();
+    assert_eq!(before, after);
+    let prefixes: Vec<_> = table.iter().map(|(p, &v)| (p.to_string(), v)).collect();
+    assert_eq!(prefixes, [
+        ("0.0.0.0/0".to_string(), 'c'),
+        ("192.168.0.0/22".to_string(), 'a'),
+        ("192.168.4.0/24".to_string(), 'b'),
+    ]);
+    assert_eq!(table.count(), 3);
+    assert_eq!(table.aggregate(), 0);
+}
+
+#[test]
+fn int_bit_test() {
+    let values = [0u64, 1, 7, 42, 1 << 40, u64::MAX, u64::MAX - 1, 123_456_789];
+    let mut tree = IntBitTrie::from_iter(values);
+    assert_eq!(tree.count(), values.len());
+    assert!(! tree.insert(42));
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    assert_eq!(tree.iter().collect::<Vec<_>>(), sorted);
+    for (k, &value) in sorted.iter().enumerate() {
+        assert_eq!(tree.kth_smallest(k), Some(value));
+        assert_eq!(tree.count_less_than(value), k);
+        assert!(tree.contains(value));
+    }
+    assert_eq!(tree.kth_smallest(sorted.len()), None);
+    assert_eq!(tree.count_less_than(u64::MAX - 2), 6);
+    for x in [0, 3, 41, 1 << 39, u64::MAX, 999] {
+        let brute_max = *values.iter().max_by_key(|&&y| x ^ y).unwrap();
+        let brute_min = *values.iter().min_by_key(|&&y| x ^ y).unwrap();
+        assert_eq!(tree.max_xor(x), Some(brute_max));
+        assert_eq!(tree.min_xor(x), Some(brute_min));
+    }
+
+    assert!(tree.remove(1 << 40));
+    assert!(! tree.remove(1 << 40));
+    assert!(! tree.contains(1 << 40));
+    assert!(tree.insert(1 << 41));
+    assert_eq!(tree.count_less_than(1 << 41), 5);
+
+    for value in tree.iter().collect::<Vec<_>>() {
+        assert!(tree.remove(value));
+    }
+    assert_eq!(tree.count(), 0);
+    assert_eq!(tree.max_xor(1), None);
+    assert_eq!(tree.iter().count(), 0);
+
+    let bytes = IntBitTrie::<u8>::from_iter(0..=255);
+    assert_eq!(bytes.count(), 256);
+    assert_eq!(bytes.max_xor(0x0f), Some(0xf0));
+    assert_eq!(format!("{:?}", IntBitTrie::<u8>::from_iter([3, 1])), "{1, 3}");
+}
+
+#[test]
+fn suffix_index_test() {
+    let texts = ["mississippi", "missouri", "sip", "", "aaaa"];
+    let index = SuffixIndex::build(texts.map(str::bytes));
+    assert_eq!(index.doc_count(), texts.len());
+    assert_eq!(index.doc(2), Some(&b"sip"[..]));
+    assert_eq!(index.doc(5), None);
+    let total: usize = texts.iter().map(|s| s.len()).sum();
+    assert!(index.node_count() <= 2 * total + 1);
+
+    let brute_occurrences = |pattern: &str| {
+        let mut res = Vec::new();
+        for (doc, text) in texts.iter().enumerate() {
+            for offset in 0..text.len() {
+                if text[offset..].starts_with(pattern) {
+                    res.push((doc, offset));
+                }
+            }
+        }
+        res
+    };
+    for pattern in ["ss", "issi", "i", "sip", "pi", "m", "miss", "aa", "aaaa",
+                    "aaaaa", "x", "ssx", "souri", "ipp", "mississippi"]
+    {
+        let expected = brute_occurrences(pattern);
+        assert_eq!(index.occurrences(pattern.bytes()), expected, "{pattern}");
+        assert_eq!(index.contains_substring(pattern.bytes()), ! expected.is_empty());
+    }
+    assert_eq!(index.occurrences("".bytes()).len(), total);
+
+    assert_eq!(index.longest_common_substring(0, 1), b"miss");
+    assert_eq!(index.longest_common_substring(1, 0), b"miss");
+    assert_eq!(index.longest_common_substring(0, 2), b"sip");
+    assert_eq!(index.longest_common_substring(0, 3), b"");
+    assert_eq!(index.longest_common_substring(0, 4), b"");
+    assert_eq!(index.longest_common_substring(4, 4), b"aaaa");
+
+    let mut index = SuffixIndex::new();
+    assert_eq!(index.push("测试中文".chars()), 0);
+    assert_eq!(index.push("中文字符".chars()), 1);
+    assert_eq!(index.occurrences(['中', '文']), [(0, 2), (1, 0)]);
+    assert_eq!(index.longest_common_substring(0, 1), ['中', '文']);
+}
+
+#[test]
+fn bi_trie_test() {
+    let words = ["testing", "string", "sting", "test", "tester", "ring", "t"];
+    let mut tree = BiTrie::from_iter(words.map(str::chars));
+    assert_eq!(tree.count(), words.len());
+    assert_eq!(tree.as_reversed().count(), words.len());
+    assert!(! tree.insert("ring".chars()));
+    assert!(tree.query("sting".chars()));
+    assert!(tree.as_reversed().query("gnits".chars()));
+
+    let suffix_heads = |tree: &BiTrie<char>, suffix: &str| {
+        let mut res: Vec<String> = tree.query_suffix_iter(suffix.chars())
+            .map(|iter| iter.map(String::from_iter).collect())
+            .unwrap_or_default();
+        res.sort();
+        res
+    };
+    assert_eq!(suffix_heads(&tree, "ing"), ["r", "st", "str", "test"]);
+    assert_eq!(suffix_heads(&tree, "t"), ["", "tes"]);
+    assert_eq!(suffix_heads(&tree, ""), tree.iter()
+        .map(String::from_iter)
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>());
+    assert!(tree.query_suffix_iter("xing".chars()).is_none());
+
+    let both = |tree: &BiTrie<char>, prefix: &str, suffix: &str| {
+        let mut res: Vec<String> = tree.prefix_and_suffix(prefix.chars(), suffix.chars())
+            .into_iter()
+            .map(String::from_iter)
+            .collect();
+        res.sort();
+        res
+    };
+    assert_eq!(both(&tree, "t", "t"), ["t", "test"]);
+    assert_eq!(both(&tree, "test", "ing"), ["testing"]);
+    assert_eq!(both(&tree, "", "ring"), ["ring", "string"]);
+    assert_eq!(both(&tree, "x", ""), Vec::<String>::new());
+    assert_eq!(both(&tree, "tester", "tester"), ["tester"]);
+
+    // 前缀与后缀分别对应较小的候选集合, 与直接过滤的结果一致
+    let nums = BiTrie::from_iter(TEST_NUMS.map(|x| x.iter().copied()));
+    for (prefix, suffix) in [(&[][..], &[26][..]), (&[1], &[]), (&[1, 3], &[5]), (&[90], &[65])] {
+        let mut res = nums.prefix_and_suffix(prefix, suffix);
+        res.sort();
+        let mut expected: Vec<_> = nums.iter()
+            .filter(|values| {
+                values.len() >= prefix.len().max(suffix.len())
+                    && values.iter().zip(prefix).all(|(a, b)| *a == b)
+                    && values.iter().rev().zip(suffix.iter().rev()).all(|(a, b)| *a == b)
+            })
+            .collect();
+        expected.sort();
+        assert!(! expected.is_empty());
+        assert_eq!(res, expected);
+    }
+
+    assert!(tree.remove("string".chars()));
+    assert!(! tree.remove("string".chars()));
+    assert_eq!(suffix_heads(&tree, "ring"), [""]);
+    assert!(! tree.as_reversed().query("gnirts".chars()));
+    assert!(tree.remove("ring".chars()));
+    assert!(tree.query_suffix_iter("ring".chars()).is_none());
+    assert_eq!(tree.as_reversed().count(), tree.count());
+
+    let cloned = tree.clone();
+    assert_eq!(cloned, tree);
+    tree.clear();
+    assert_eq!(tree.count(), 0);
+    assert!(tree.query_suffix_iter("t".chars()).is_none());
+    assert_ne!(cloned, tree);
+}
+
+#[test]
+fn normalizing_trie_test() {
+    assert_eq!(StripAccents.normalize_all("ÀÉÎõüÇñŸžŁøß".chars()), "AEIouCnYzŁøß".chars().collect::<Vec<_>>());
+    assert_eq!(StripAccents.normalize_all("e\u{301}a\u{308}".chars()), ['e', 'a']);
+    assert_eq!(CaseFold.normalize_all("ÄBc".chars()), ['ä', 'b', 'c']);
+    assert_eq!((CaseFold, StripAccents).normalize_all("ÉCOLE".chars()), "ecole".chars().collect::<Vec<_>>());
+
+    let mut tree = NormalizingTrie::new((CaseFold, StripAccents));
+    tree.extend(["Éclair", "eclair", "École", "ecole", "Ecologie"].map(str::chars));
+    assert!(! tree.insert("Éclair".chars()));
+    assert_eq!(tree.count(), 5);
+    assert_eq!(tree.normalized_count(), 3);
+    assert!(tree.contains("ECLAIR".chars()));
+    assert!(! tree.contains("ECLAI".chars()));
+    assert!(tree.starts_with("ÉCL".chars()));
+    assert!(! tree.starts_with("ECX".chars()));
+
+    let strings = |keys: &[Vec<char>]| keys.iter().map(String::from_iter).collect::<Vec<_>>();
+    assert_eq!(strings(tree.get("eclair".chars()).unwrap()), ["Éclair", "eclair"]);
+    assert!(tree.get("ecl".chars()).is_none());
+    let mut words: Vec<String> = tree.completions("ÉCO".chars()).map(String::from_iter).collect();
+    words.sort();
+    assert_eq!(words, ["Ecologie", "ecole", "École"]);
+    assert_eq!(tree.completions("x".chars()).count(), 0);
+    assert_eq!(tree.iter().count(), 5);
+
+    assert!(tree.remove_original("ecole".chars()));
+    assert!(! tree.remove_original("ecole".chars()));
+    assert!(tree.contains("ecole".chars()));
+    assert!(tree.remove_original("École".chars()));
+    assert!(! tree.contains("ecole".chars()));
+    assert!(tree.starts_with("ecol".chars()));
+    assert_eq!(tree.count(), 3);
+    let removed = tree.remove("ECLAIR".chars()).unwrap();
+    assert_eq!(strings(&removed), ["Éclair", "eclair"]);
+    assert!(tree.remove("ECLAIR".chars()).is_none());
+    assert!(! tree.starts_with("ecl".chars()));
+    assert_eq!(tree.count(), 1);
+    assert_eq!(tree.normalized_count(), 1);
+    assert_eq!(tree.iter().map(String::from_iter).collect::<Vec<_>>(), ["Ecologie"]);
+
+    // 自定义映射, 忽略连字符并将全角数字映射为半角数字
+    let mut codes = NormalizingTrie::new(|ch: &char| match ch {
+        '-' => None,
+        '０'..='９' => char::from_u32(*ch as u32 - '０' as u32 + '0' as u32),
+        _ => Some(*ch),
+    });
+    assert!(codes.insert("12-34".chars()));
+    assert!(codes.contains("１２３４".chars()));
+    assert!(codes.contains("1-2-3-4".chars()));
+    assert!(codes.insert("1234".chars()));
+    assert_eq!(codes.normalized_count(), 1);
+}
+
+#[test]
+fn segment_test() {
+    let tree = TrieTree::from_iter(
+        ["结婚", "的", "和", "尚未", "结婚", "和尚", "未"].map(str::chars));
+    let text = "结婚的和尚未结婚的";
+    let words = |mode| -> Vec<&str> {
+        tree.segment(text, mode).into_iter().map(|span| &text[span]).collect()
+    };
+    assert_eq!(words(SegmentMode::ForwardMax),
+               ["结婚", "的", "和尚", "未", "结婚", "的"]);
+    assert_eq!(words(SegmentMode::BackwardMax),
+               ["结婚", "的", "和", "尚未", "结婚", "的"]);
+    assert_eq!(words(SegmentMode::Bidirectional),
+               ["结婚", "的", "和", "尚未", "结婚", "的"]);
+
+    // 未知字符作为单独的词, 字节区间覆盖整个文本
+    let text = "a结婚b和尚";
+    for mode in [SegmentMode::ForwardMax, SegmentMode::BackwardMax, SegmentMode::Bidirectional] {
+        let spans = tree.segment(text, mode);
+        let words: Vec<&str> = spans.iter().map(|span| &text[span.clone()]).collect();
+        assert_eq!(words, ["a", "结婚", "b", "和尚"]);
+        assert_eq!(spans.first().unwrap().start, 0);
+        assert_eq!(spans.last().unwrap().end, text.len());
+        assert!(spans.windows(2).all(|pair| pair[0].end == pair[1].start));
+    }
+    assert!(tree.segment("", SegmentMode::Bidirectional).is_empty());
+
+    // 词数与单字符数都相同时优先逆向结果
+    let tree = TrieTree::from_iter(["ab", "cd", "bc"].map(str::chars));
+    let text = "abcd";
+    let words = |mode| -> Vec<&str> {
+        tree.segment(text, mode).into_iter().map(|span| &text[span]).collect()
+    };
+    assert_eq!(words(SegmentMode::ForwardMax), ["ab", "cd"]);
+    assert_eq!(words(SegmentMode::BackwardMax), ["ab", "cd"]);
+    let text = "abcx";
+    let words = |mode| -> Vec<&str> {
+        tree.segment(text, mode).into_iter().map(|span| &text[span]).collect()
+    };
+    assert_eq!(words(SegmentMode::ForwardMax), ["ab", "c", "x"]);
+    assert_eq!(words(SegmentMode::BackwardMax), ["a", "bc", "x"]);
+    assert_eq!(words(SegmentMode::Bidirectional), ["a", "bc", "x"]);
+}
+
+#[test]
+fn lattice_test() {
+    let tree = TrieTree::from_iter(["北京", "北京大学", "大学", "大学生", "学生", "生活"].map(str::chars));
+    let text = "北京大学生活x";
+    let lattice = tree.build_lattice(text);
+    assert_eq!(lattice.text(), text);
+    let edges: Vec<(&str, bool)> = lattice.edges().map(|edge| (edge.word, edge.is_word)).collect();
+    assert_eq!(edges, [
+        ("北京", true), ("北京大学", true), ("京", false),
+        ("大学", true), ("大学生", true), ("学生", true),
+        ("生活", true), ("活", false), ("x", false),
+    ]);
+    assert!(lattice.edges().all(|edge| edge.weight.is_none()));
+    assert!(lattice.edges().all(|edge| &text[edge.span.clone()] == edge.word));
+
+    // 每个词权重相同时, 路径的词数越少越好
+    let words = |path: Vec<core::ops::Range<usize>>| -> Vec<&str> {
+        path.into_iter().map(|span| &text[span]).collect()
+    };
+    let path = lattice.best_path(|edge| if edge.is_word { -1.0 } else { -10.0 });
+    assert_eq!(words(path), ["北京大学", "生活", "x"]);
+    let path = lattice.best_path(|edge| match edge.word {
+        "北京" | "大学生" => -1.0,
+        _ => -5.0,
+    });
+    assert_eq!(words(path), ["北京", "大学生", "活", "x"]);
+
+    let mut dict = WeightedTrie::from_iter([
+        ("北京", -4.0), ("北京大学", -10.0), ("大学", -5.0),
+        ("大学生", -6.0), ("学生", -5.0), ("生活", -5.0),
+    ]);
+    assert_eq!(dict.count(), 6);
+    let best = |dict: &WeightedTrie| {
+        let path = dict.build_lattice(text).best_path(|edge| edge.weight.unwrap_or(-20.0));
+        words(path)
+    };
+    assert_eq!(best(&dict), ["北京", "大学", "生活", "x"]);
+    assert_eq!(dict.insert("北京大学", -3.0), Some(-10.0));
+    assert_eq!(best(&dict), ["北京大学", "生活", "x"]);
+    let node_count = dict.node_count();
+    assert_eq!(dict.remove("北京大学"), Some(-3.0));
+    assert_eq!(dict.remove("北京大学"), None);
+    assert_eq!(dict.node_count(), node_count - 2);
+    assert_eq!(dict.insert("北京大学", -3.0), None);
+    assert_eq!(dict.node_count(), node_count);
+    assert_eq!(dict.remove("北京大学"), Some(-3.0));
+    assert!(! dict.contains("北京大学"));
+    assert!(dict.contains("北京"));
+    assert_eq!(dict.count(), 5);
+    assert_eq!(best(&dict), ["北京", "大学", "生活", "x"]);
+
+    assert!(dict.build_lattice("").best_path(|_| 0.0).is_empty());
+    dict.clear();
+    assert_eq!(dict.count(), 0);
+    assert_eq!(dict.get("北京"), None);
+}
+
+#[test]
+fn filter_test() {
+    let tree = TrieTree::from_iter(["ab", "abcd", "bc", "c", "xy"].map(str::chars));
+    let text = "zabcdxy";
+    let find = |options| -> Vec<(usize, usize)> {
+        tree.find_all(text, options).map(|m| (m.start, m.end)).collect()
+    };
+    assert_eq!(find(MatchOptions::new()), [(1, 5), (5, 7)]);
+    assert_eq!(find(MatchOptions::new().kind(MatchKind::LeftmostShortest)),
+               [(1, 3), (3, 4), (5, 7)]);
+    assert_eq!(find(MatchOptions::new().overlapping(true)),
+               [(1, 3), (1, 5), (2, 4), (3, 4), (5, 7)]);
+    assert_eq!(tree.find_all("", MatchOptions::new()).count(), 0);
+    assert_eq!(tree.find_all("zzz", MatchOptions::new()).count(), 0);
+
+    // 跳过空白与标点, 匹配不以跳过的字符开始, 也不以其结束
+    let options = MatchOptions::new().skip(|ch| ch == ' ' || ch == '-');
+    let text = " a-b c- x y";
+    let found: Vec<_> = tree.find_all(text, options).collect();
+    assert_eq!(found, [Match { start: 1, end: 4 }, Match { start: 5, end: 6 }, Match { start: 8, end: 11 }]);
+    assert_eq!(tree.mask(text, options, '*'), " *** *- ***");
+    assert_eq!(tree.mask(text, MatchOptions::new(), '*'), " a-b *- x y");
+
+    // 多字节字符
+    let tree = TrieTree::from_iter(["敏感", "感词", "坏"].map(str::chars));
+    let text = "这是敏感词, 很坏!";
+    assert_eq!(tree.mask(text, MatchOptions::new(), '*'), "这是**词, 很*!");
+    assert_eq!(tree.mask(text, MatchOptions::new().overlapping(true), '*'), "这是***, 很*!");
+    let res = tree.replace_all(text, MatchOptions::new().overlapping(true), |m| {
+        format!("[{}]", &text[m.range()])
+    });
+    assert_eq!(res, "这是[敏感]词, 很[坏]!");
+    let res = tree.replace_all(text, MatchOptions::new(), |_| "");
+    assert_eq!(res, "这是词, 很!");
+
+    // 按照插入顺序选择同一位置开始的词
+    let mut filter = WordFilter::from_iter(["abcd", "c", "ab", "bc", "xy"]);
+    assert!(! filter.insert("ab"));
+    assert_eq!(filter.count(), 5);
+    let text = "zabcdxy";
+    let find = |filter: &WordFilter, kind| -> Vec<(usize, usize)> {
+        filter.find_all(text, MatchOptions::new().kind(kind)).map(|m| (m.start, m.end)).collect()
+    };
+    assert_eq!(find(&filter, MatchKind::LeftmostFirst), [(1, 5), (5, 7)]);
+    assert_eq!(find(&filter, MatchKind::LeftmostShortest), [(1, 3), (3, 4), (5, 7)]);
+    assert_eq!(find(&filter, MatchKind::LeftmostLongest), [(1, 5), (5, 7)]);
+    assert!(filter.remove("abcd"));
+    assert!(! filter.contains("abcd"));
+    assert!(filter.insert("abcd"));
+    assert_eq!(find(&filter, MatchKind::LeftmostFirst), [(1, 3), (3, 4), (5, 7)]);
+    assert_eq!(filter.mask(text, MatchOptions::new().kind(MatchKind::LeftmostFirst), '*'), "z***d**");
+    filter.clear();
+    assert_eq!(filter.find_all(text, MatchOptions::new()).count(), 0);
+    // 树不记录插入顺序, 与最短匹配相同
+    let tree = TrieTree::from_iter(["abcd", "ab"].map(str::chars));
+    let first: Vec<_> = tree.find_all(text, MatchOptions::new().kind(MatchKind::LeftmostFirst)).collect();
+    assert_eq!(first, [Match { start: 1, end: 3 }]);
+}
+
+#[test]
+fn aho_corasick_test() {
+    let patterns = ["a", "ab", "bab", "bc", "bca", "c", "caa", ""];
+    let tree = TrieTree::from_iter(patterns.map(str::bytes));
+    let ac = tree.to_aho_corasick();
+    assert_eq!(ac.pattern_count(), patterns.len() - 1);
+    assert_eq!(ac.state_count(), tree.stats().node_count);
+
+    let brute = |text: &[u8]| {
+        let mut res = Vec::new();
+        for end in 1..=text.len() {
+            for start in 0..end {
+                if tree.query(&text[start..end]) {
+                    res.push(Match { start, end });
+                }
+            }
+        }
+        res
+    };
+    for text in ["abccab", "bababcaab", "", "xyz", "caacaab", "aaaa"] {
+        let found: Vec<_> = ac.find_iter(text.bytes()).collect();
+        assert_eq!(found, brute(text.as_bytes()), "{text}");
+        assert_eq!(ac.is_match(text.as_bytes()), ! found.is_empty());
+    }
+
+    // 非字节的泛型元素
+    let tree = TrieTree::from_iter([vec![1u32, 2, 3], vec![2, 3], vec![3, 4, 5, 6]]);
+    let ac = tree.to_aho_corasick();
+    let found: Vec<_> = ac.find_iter([0, 1, 2, 3, 4, 5, 6, 2, 3]).collect();
+    assert_eq!(found, [
+        Match { start: 1, end: 4 },
+        Match { start: 2, end: 4 },
+        Match { start: 3, end: 7 },
+        Match { start: 7, end: 9 },
+    ]);
+    let empty = TrieTree::<u32>::new().to_aho_corasick();
+    assert_eq!(empty.find_iter([1, 2, 3]).count(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn stream_scanner_test() {
+    use std::io::{BufReader, Read};
+
+    let tree = TrieTree::from_iter(["abcab", "bca", "cabca", "a"].map(str::bytes));
+    let ac = tree.to_aho_corasick();
+    let data = b"xabcabcabcax".repeat(50);
+    let expected: Vec<StreamMatch> = ac.find_iter(&data)
+        .map(|m| StreamMatch { start: m.start as u64, end: m.end as u64 })
+        .collect();
+    assert!(! expected.is_empty());
+    for capacity in [1, 2, 3, 5, 7, 64, 4096] {
+        let reader = BufReader::with_capacity(capacity, &data[..]);
+        let mut scanner = StreamScanner::new(&ac, reader);
+        let found: Vec<StreamMatch> = scanner.by_ref().collect::<Result<_, _>>().unwrap();
+        assert_eq!(found, expected, "capacity {capacity}");
+        assert_eq!(scanner.offset(), data.len() as u64);
+    }
+
+    // 在匹配后停止扫描, 剩余的数据仍在读取器中
+    let reader = BufReader::with_capacity(4, &b"..bca.."[..]);
+    let mut scanner = StreamScanner::new(&ac, reader);
+    assert_eq!(scanner.next().unwrap().unwrap(), StreamMatch { start: 2, end: 5 });
+    assert_eq!(scanner.next().unwrap().unwrap(), StreamMatch { start: 4, end: 5 });
+    assert_eq!(scanner.offset(), 5);
+    let mut rest = String::new();
+    scanner.into_inner().read_to_string(&mut rest).unwrap();
+    assert_eq!(rest, "..");
+
+    // 读取错误被传递
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("broken"))
+        }
+    }
+    let mut scanner = StreamScanner::new(&ac, BufReader::new(Failing));
+    assert!(scanner.next().unwrap().is_err());
+}
@@ -0,0 +1,205 @@
+//! 以二进制位为键的节点
+//! 键的位从高到低左对齐存储在`u128`中, 只有前`len`位有效
+
+use core::mem;
+use alloc::{boxed::Box, vec, vec::Vec};
+
+/// 获取左对齐的位串中第`index`位
+pub(crate) fn bit_at(bits: u128, index: u8) -> usize {
+    (bits >> (127 - index) & 1) as usize
+}
+
+/// 只保留左对齐的位串的前`len`位
+pub(crate) fn mask_bits(bits: u128, len: u8) -> u128 {
+    match len {
+        0 => 0,
+        _ => bits & (u128::MAX << (128 - len as u32)),
+    }
+}
+
+/// 二叉的`TrieNode`, 每个节点可以存储一个值
+pub(crate) struct BitNode<V> {
+    pub value: Option<V>,
+    pub childs: [Option<Box<Self>>; 2],
+}
+
+impl<V> Default for BitNode<V> {
+    fn default() -> Self {
+        Self { value: None, childs: [None, None] }
+    }
+}
+
+impl<V> BitNode<V> {
+    /// 是否没有值且没有子节点
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none() && self.childs.iter().all(Option::is_none)
+    }
+
+    /// 查询位串末尾处的节点
+    pub fn query_node(&self, bits: u128, len: u8) -> Option<&Self> {
+        let mut node = self;
+        for i in 0..len {
+            node = node.childs[bit_at(bits, i)].as_deref()?;
+        }
+        Some(node)
+    }
+
+    pub fn query_node_mut(&mut self, bits: u128, len: u8) -> Option<&mut Self> {
+        let mut node = self;
+        for i in 0..len {
+            node = node.childs[bit_at(bits, i)].as_deref_mut()?;
+        }
+        Some(node)
+    }
+
+    /// 查询位串末尾处的节点, 不存在的节点将被创建
+    pub fn get_or_insert_node(&mut self, bits: u128, len: u8) -> &mut Self {
+        let mut node = self;
+        for i in 0..len {
+            node = node.childs[bit_at(bits, i)].get_or_insert_with(Box::default);
+        }
+        node
+    }
+
+    /// 删除位串处的值, 并清理悬垂的节点
+    pub fn remove(&mut self, bits: u128, len: u8) -> Option<V> {
+        let value = self.query_node_mut(bits, len)?.value.take()?;
+        // 找到最浅的可以删除的节点, 它与其下路径上的节点都没有值且没有其他分支
+        // 根节点不会被删除
+        let mut start = None;
+        let mut node = &*self;
+        for i in 0..len {
+            let bit = bit_at(bits, i);
+            if i == 0 || node.value.is_some() || node.childs[bit ^ 1].is_some() {
+                start = None;
+            } else if start.is_none() {
+                start = Some(i);
+            }
+            node = node.childs[bit].as_deref().unwrap();
+        }
+        if len == 0 || ! node.is_empty() {
+            return Some(value);
+        }
+        let cut = start.unwrap_or(len) - 1;
+        let parent = self.query_node_mut(bits, cut).unwrap();
+        parent.childs[bit_at(bits, cut)] = None;
+        Some(value)
+    }
+
+    /// 按照前序遍历产生所有值, 子节点按照位升序
+    pub fn iter(&self, bits: u128, len: u8) -> BitIter<'_, V> {
+        BitIter { stack: vec![(self, bits, len)] }
+    }
+
+    /// 产生位串路径上的所有值, 从短到长
+    pub fn path_values(
+        &self,
+        bits: u128,
+        len: u8,
+    ) -> impl Iterator<Item = (u8, &V)> {
+        let mut node = Some(self);
+        let mut depth = 0;
+        core::iter::from_fn(move || loop {
+            let current = node?;
+            let current_depth = depth;
+            node = if depth < len {
+                depth += 1;
+                current.childs[bit_at(bits, current_depth)].as_deref()
+            } else {
+                None
+            };
+            if let Some(value) = &current.value {
+                return Some((current_depth, value));
+            }
+        })
+    }
+}
+
+impl<V: PartialEq> BitNode<V> {
+    /// 聚合前缀, 返回减少的值的数量
+    /// - 删除与最近的祖先值相同的值
+    /// - 将值相同的两个兄弟节点的值合并到没有值的父节点
+    pub fn aggregate(&mut self) -> usize {
+        /// 正在处理的节点, 其子节点被暂时取出, 处理后放回
+        struct Frame<V> {
+            node: Box<BitNode<V>>,
+            next: usize,
+            /// 最近的有值的祖先的帧下标
+            cover: Option<usize>,
+            bit: usize,
+        }
+
+        let mut removed = 0;
+        let root = Box::new(mem::take(self));
+        let mut stack = vec![Frame { node: root, next: 0, cover: None, bit: 0 }];
+        // 使用显式栈后序遍历
+        loop {
+            let top = stack.len() - 1;
+            let frame = &mut stack[top];
+            if frame.next < 2 {
+                let bit = frame.next;
+                frame.next += 1;
+                if let Some(node) = frame.node.childs[bit].take() {
+                    let cover = if frame.node.value.is_some() { Some(top) } else { frame.cover };
+                    stack.push(Frame { node, next: 0, cover, bit });
+                }
+                continue;
+            }
+            let Frame { mut node, cover, bit, .. } = stack.pop().unwrap();
+            if node.value.is_none() {
+                if let [Some(a), Some(b)] = &mut node.childs {
+                    if a.value.is_some() && a.value == b.value {
+                        node.value = a.value.take();
+                        b.value = None;
+                        removed += 1;
+                        for child in &mut node.childs {
+                            if child.as_ref().is_some_and(|child| child.is_empty()) {
+                                *child = None;
+                            }
+                        }
+                    }
+                }
+            }
+            if let (Some(value), Some(cover)) = (&node.value, cover) {
+                if stack[cover].node.value.as_ref() == Some(value) {
+                    node.value = None;
+                    removed += 1;
+                }
+            }
+            match stack.last_mut() {
+                Some(parent) => if ! node.is_empty() {
+                    parent.node.childs[bit] = Some(node);
+                },
+                None => {
+                    *self = mem::take(&mut *node);
+                    break removed;
+                },
+            }
+        }
+    }
+}
+
+/// 前序遍历`BitNode`的迭代器, 产生位串, 长度以及值
+pub(crate) struct BitIter<'a, V> {
+    stack: Vec<(&'a BitNode<V>, u128, u8)>,
+}
+
+impl<'a, V> Iterator for BitIter<'a, V> {
+    type Item = (u128, u8, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, bits, len) = self.stack.pop()?;
+            // 先压入位为1的子节点, 使位为0的子节点先被遍历
+            for bit in [1, 0] {
+                if let Some(child) = &node.childs[bit] {
+                    let bits = bits | (bit as u128) << (127 - len);
+                    self.stack.push((child, bits, len + 1));
+                }
+            }
+            if let Some(value) = &node.value {
+                return Some((bits, len, value));
+            }
+        }
+    }
+}
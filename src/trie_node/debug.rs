@@ -1,4 +1,9 @@
-use std::fmt::{Debug, Write};
+use core::{
+    fmt::{Debug, Write},
+    iter::Peekable,
+};
+use alloc::vec;
+
 use super::TrieNode;
 
 enum NodeData<T> {
@@ -9,7 +14,7 @@ enum NodeData<T> {
 impl<T> Debug for NodeData<T>
 where T: Debug
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Self::Solid(data) => data.fmt(f),
             Self::Root => f.write_char('/'),
@@ -22,10 +27,10 @@ impl<T> From<T> for NodeData<T> {
     }
 }
 
-impl<T> Debug for TrieNode<T>
+impl<T, S> Debug for TrieNode<T, S>
 where T: Debug,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         const INDENT: &str = "    ";
         const CR: &str = "\n";
         const SEP: &str = ",";
@@ -37,32 +42,14 @@ where T: Debug,
         const CLOSE_NOSTOP: char = ')';
         const COLON: &str = ":";
 
-        fn do_fmt<T>(
-            self_: &TrieNode<T>,
+        /// 写入节点头部, 返回该节点是否有子节点
+        fn write_head<T, S>(
+            self_: &TrieNode<T, S>,
             self_data: NodeData<&T>,
-            f: &mut std::fmt::Formatter<'_>,
-            level: usize,
-            ) -> std::fmt::Result
+            f: &mut core::fmt::Formatter<'_>,
+            ) -> Result<bool, core::fmt::Error>
         where T: Debug
         {
-            let (indent, nindent, sep, cr, colon)
-                = if f.alternate() {
-                    (
-                        INDENT.repeat(level),
-                        INDENT.repeat(level + 1),
-                        format!("{} ", SEP),
-                        CR,
-                        format!("{} ", COLON),
-                        )
-                } else {
-                    (
-                        "".to_string(),
-                        "".to_string(),
-                        SEP.to_string(),
-                        "",
-                        COLON.to_string(),
-                        )
-                };
             if self_.stop() {
                 // is stop node
                 f.write_char(OPEN_STOP)?;
@@ -74,28 +61,77 @@ where T: Debug,
                 self_data.fmt(f)?;
                 f.write_char(CLOSE_NOSTOP)?;
             }
-            f.write_str(&colon)?;
+            f.write_str(COLON)?;
+            if f.alternate() {
+                f.write_char(' ')?;
+            }
             f.write_char(OPEN)?;
-            let mut childs
-                = self_.childs().iter().peekable();
-            if childs.peek().is_none() {
+            if self_.is_empty() {
                 // no element
-                return f.write_char(CLOSE)
+                f.write_char(CLOSE)?;
+                return Ok(false);
+            }
+            if f.alternate() {
+                f.write_str(CR)?;
+            }
+            Ok(true)
+        }
+
+        fn write_indent(
+            f: &mut core::fmt::Formatter<'_>,
+            level: usize,
+            ) -> core::fmt::Result
+        {
+            if f.alternate() {
+                for _ in 0..level {
+                    f.write_str(INDENT)?;
+                }
             }
-            f.write_str(cr)?;
-            while let Some((data, node)) = childs.next() {
-                f.write_str(&nindent)?;
-                do_fmt(node, data.into(), f, level + 1)?;
-                if childs.peek().is_some() {
-                    // 非即将结束
-                    f.write_str(&sep)?;
+            Ok(())
+        }
+
+        /// 如果后面还有兄弟节点则写入分隔符, 然后写入换行
+        fn write_sep<I: Iterator>(
+            childs: &mut Peekable<I>,
+            f: &mut core::fmt::Formatter<'_>,
+            ) -> core::fmt::Result
+        {
+            if childs.peek().is_some() {
+                // 非即将结束
+                f.write_str(SEP)?;
+                if f.alternate() {
+                    f.write_char(' ')?;
                 }
-                f.write_str(cr)?;
             }
-            f.write_str(&indent)?;
-            f.write_char(CLOSE)?;
+            if f.alternate() {
+                f.write_str(CR)?;
+            }
             Ok(())
         }
-        do_fmt(self, NodeData::Root, f, 0)
+
+        if ! write_head(self, NodeData::Root, f)? {
+            return Ok(());
+        }
+        let mut stack = vec![self.childs().iter().peekable()];
+        while ! stack.is_empty() {
+            let level = stack.len();
+            let childs = stack.last_mut().unwrap();
+            if let Some((data, node)) = childs.next() {
+                write_indent(f, level)?;
+                if write_head(node, data.into(), f)? {
+                    stack.push(node.childs().iter().peekable());
+                } else {
+                    write_sep(childs, f)?;
+                }
+            } else {
+                stack.pop();
+                write_indent(f, level - 1)?;
+                f.write_char(CLOSE)?;
+                if let Some(parent) = stack.last_mut() {
+                    write_sep(parent, f)?;
+                }
+            }
+        }
+        Ok(())
     }
 }
@@ -1,20 +1,20 @@
-use super::TrieNode;
-use std::{
-    collections::hash_map::Iter as HashMapIter,
-    fmt::Debug,
-};
+use super::{TrieNode, ChildsIter};
+use core::fmt::Debug;
+use alloc::vec::Vec;
+
+use crate::collections::RandomState;
 
 /// Iterator used to iterate over `TrieNode`
 #[derive(Debug, Clone)]
-pub struct Iter<'a, T> {
+pub struct Iter<'a, T, S = RandomState> {
     /// 存储每个节点以及其是否为终节点
     /// 当获取该值后将是否为终节点设置为否
-    nodes: Vec<(bool, HashMapIter<'a, T, TrieNode<T>>)>,
+    nodes: Vec<(bool, ChildsIter<'a, T, S>)>,
     /// 存储每个值
     datas: Vec<&'a T>,
 }
 
-impl<'a, T> Iter<'a, T> {
+impl<'a, T, S> Iter<'a, T, S> {
     /// do `self.next_op(|x| x)`
     /// # Examples
     /// ```
@@ -48,28 +48,29 @@ impl<'a, T> Iter<'a, T> {
     pub fn next_op<'b, R, F>(&'b mut self, f: F) -> Option<R>
     where F: FnOnce(&'b Vec<&'a T>) -> R
     {
-        // 节点以及该节点的值迭代器
-        let (is_stop, kvs)
-            = self.nodes.last_mut()?;
-        Some(if *is_stop {
-            *is_stop = false;
-            f(&self.datas)
-        } else if let Some((data, node)) = kvs.next() {
-            // 在末端迭代到下一个分支
-            self.datas.push(data);
-            self.nodes.push((node.stop(), node.childs().iter()));
-            self.next_op(f)?
-        } else {
-            // 末端迭代到了底部
-            // 删除该端, 它已没有更多子节点也不是一个终止节点了
-            self.datas.pop()?;
-            self.nodes.pop()?;
-            self.next_op(f)?
-        })
+        loop {
+            // 节点以及该节点的值迭代器
+            let (is_stop, kvs)
+                = self.nodes.last_mut()?;
+            if *is_stop {
+                *is_stop = false;
+                break;
+            } else if let Some((data, node)) = kvs.next() {
+                // 在末端迭代到下一个分支
+                self.datas.push(data);
+                self.nodes.push((node.stop(), node.childs().iter()));
+            } else {
+                // 末端迭代到了底部
+                // 删除该端, 它已没有更多子节点也不是一个终止节点了
+                self.datas.pop()?;
+                self.nodes.pop()?;
+            }
+        }
+        Some(f(&self.datas))
     }
 }
 
-impl<'a, T> Iterator for Iter<'a, T>
+impl<'a, T, S> Iterator for Iter<'a, T, S>
 {
     type Item = Vec<&'a T>;
 
@@ -78,8 +79,15 @@ impl<'a, T> Iterator for Iter<'a, T>
     }
 }
 
-impl<'a, T> From<&'a TrieNode<T>> for Iter<'a, T> {
-    fn from(node: &'a TrieNode<T>) -> Self {
+impl<T, S> Default for Iter<'_, T, S> {
+    /// an iterator that yields nothing
+    fn default() -> Self {
+        Self { nodes: Vec::new(), datas: Vec::new() }
+    }
+}
+
+impl<'a, T, S> From<&'a TrieNode<T, S>> for Iter<'a, T, S> {
+    fn from(node: &'a TrieNode<T, S>) -> Self {
         #![allow(clippy::vec_init_then_push)]
         // 刻意的去使用`Vec::new()`而不是`vec![]`宏
         // 来避免一些可能的效率影响
@@ -1,63 +1,151 @@
+//! 所有树的公共节点
+//!
+//! 键的长度不受限制, 节点的深度可以远大于线程栈能承受的递归深度,
+//! 因此本模块及其他模块中遍历节点的算法(比较, 复制, 析构, 格式化, 解析, 合并等)
+//! 一律使用显式栈或循环, 不使用递归; 新增的遍历也应当遵守这一约定
+
 pub(crate) mod debug;
 pub(crate) mod iter;
+pub(crate) mod bit;
 
-use std::{
-    collections::HashMap,
+use core::{
     borrow::Borrow,
-    hash::Hash
+    convert::Infallible,
+    hash::{BuildHasher, Hash},
 };
+use alloc::{vec, vec::Vec};
+
+use crate::collections::{HashMap, HashMapIter, RandomState};
 
 use crate::TrieTree;
 use self::iter::Iter;
 
+/// 子节点哈希表的迭代器
+pub(crate) type ChildsIter<'a, T, S> = HashMapIter<'a, T, TrieNode<T, S>>;
 
-pub(crate) struct TrieNode<T> {
+pub(crate) struct TrieNode<T, S = RandomState> {
     /// 是否为一个停止节点
     stop: bool,
-    /// 子节点们
-    childs: HashMap<T, Self>
+    /// 子节点们, 每个子节点的哈希表都使用该哈希表的哈希构建器的克隆
+    childs: HashMap<T, Self, S>
 }
 
-impl<T: Hash + Eq> Eq for TrieNode<T> {}
+impl<T: Hash + Eq, S: BuildHasher> Eq for TrieNode<T, S> {}
 
-impl<T> PartialEq for TrieNode<T>
-where T: Hash + Eq
+impl<T, S> PartialEq for TrieNode<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.stop() == other.stop()
-            && self.childs() == other.childs()
+        let mut stack = vec![(self, other)];
+        while let Some((a, b)) = stack.pop() {
+            if a.stop() != b.stop() || a.childs().len() != b.childs().len() {
+                return false;
+            }
+            for (data, child) in a.childs() {
+                match b.get_child(data) {
+                    Some(other_child) => stack.push((child, other_child)),
+                    None => return false,
+                }
+            }
+        }
+        true
     }
 }
 
 
-impl<T> Clone for TrieNode<T>
-where T: Clone,
+impl<T, S> Clone for TrieNode<T, S>
+where T: Hash + Eq + Clone,
+      S: BuildHasher + Clone,
 {
     fn clone(&self) -> Self {
-        Self {
-            stop: self.stop(),
-            childs: self.childs().clone(),
+        /// 正在复制的节点
+        struct Frame<'a, T, S> {
+            /// 该节点在父节点中的键, 根节点为`None`
+            data: Option<T>,
+            stop: bool,
+            iter: ChildsIter<'a, T, S>,
+            /// 已经复制完成的子节点
+            childs: HashMap<T, TrieNode<T, S>, S>,
+        }
+        impl<'a, T, S: Clone> Frame<'a, T, S> {
+            fn new(data: Option<T>, node: &'a TrieNode<T, S>) -> Self {
+                Self {
+                    data,
+                    stop: node.stop(),
+                    iter: node.childs().iter(),
+                    childs: HashMap::with_capacity_and_hasher(
+                        node.childs().len(),
+                        node.childs().hasher().clone(),
+                    ),
+                }
+            }
+        }
+
+        // 后根序复制, 子节点复制完成后再插入父节点
+        let mut stack = vec![Frame::new(None, self)];
+        loop {
+            let frame = stack.last_mut().unwrap();
+            if let Some((data, node)) = frame.iter.next() {
+                stack.push(Frame::new(Some(data.clone()), node));
+                continue;
+            }
+            let frame = stack.pop().unwrap();
+            let node = Self {
+                stop: frame.stop,
+                childs: frame.childs,
+            };
+            match (frame.data, stack.last_mut()) {
+                (Some(data), Some(parent)) => {
+                    parent.childs.insert(data, node);
+                },
+                _ => break node,
+            }
         }
     }
 }
 
-impl<T> Default for TrieNode<T> {
-    fn default() -> Self {
-        Self {
-            stop: false,
-            childs: HashMap::with_capacity(0),
+impl<T, S> Drop for TrieNode<T, S> {
+    fn drop(&mut self) {
+        if self.childs.is_empty() {
+            return;
+        }
+        // 将子孙节点逐个取出后再释放, 每个节点析构时已没有子节点
+        let mut stack: Vec<Self> = self.childs.drain()
+            .map(|(_, node)| node)
+            .collect();
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.childs.drain().map(|(_, node)| node));
         }
     }
 }
 
+impl<T, S> Default for TrieNode<T, S>
+where S: Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
 impl<T> TrieNode<T> {
     #[allow(unused)]
     pub fn new() -> Self {
         Self::default()
     }
+}
+
+impl<T, S> TrieNode<T, S> {
+    /// 使用指定的哈希构建器创建节点
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            stop: false,
+            childs: HashMap::with_capacity_and_hasher(0, hasher),
+        }
+    }
 
     /// 设置为终节点
-    fn set_stop(&mut self) {
+    pub(crate) fn set_stop(&mut self) {
         self.stop = true
     }
 
@@ -88,17 +176,17 @@ impl<T> TrieNode<T> {
     }
 
     /// 获取子节点的哈希表
-    pub fn childs(&self) -> &HashMap<T, Self> {
+    pub fn childs(&self) -> &HashMap<T, Self, S> {
         &self.childs
     }
 
     /// 获取子节点的可变哈希表
-    pub fn childs_mut(&mut self) -> &mut HashMap<T, Self> {
+    pub fn childs_mut(&mut self) -> &mut HashMap<T, Self, S> {
         &mut self.childs
     }
 
     /// 获取该节点的迭代器
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T, S> {
         self.into()
     }
 
@@ -107,9 +195,9 @@ impl<T> TrieNode<T> {
     pub fn map_nodes_first_root<F>(&mut self, f: &mut F)
     where F: FnMut(&mut Self)
     {
-        f(self);
-        self.childs_mut().values_mut().for_each(|child| {
-            child.map_nodes_first_root(f);
+        let _ = self.try_map_nodes_first_root(&mut |node| {
+            f(node);
+            Ok::<_, Infallible>(())
         });
     }
 
@@ -118,10 +206,10 @@ impl<T> TrieNode<T> {
     pub fn map_nodes_last_root<F>(&mut self, f: &mut F)
     where F: FnMut(&mut Self)
     {
-        self.childs_mut().values_mut().for_each(|child| {
-            child.map_nodes_last_root(f)
+        let _ = self.try_map_nodes_last_root(&mut |node| {
+            f(node);
+            Ok::<_, Infallible>(())
         });
-        f(self);
     }
 
     /// 使用指定函数先根序操作每个节点
@@ -130,9 +218,10 @@ impl<T> TrieNode<T> {
     pub fn try_map_nodes_first_root<F, E>(&mut self, f: &mut F) -> Result<(), E>
     where F: FnMut(&mut Self) -> Result<(), E>
     {
-        f(self)?;
-        for child in self.childs_mut().values_mut() {
-            child.try_map_nodes_first_root(f)?
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            f(node)?;
+            stack.extend(node.childs_mut().values_mut());
         }
         Ok(())
     }
@@ -143,24 +232,33 @@ impl<T> TrieNode<T> {
     pub fn try_map_nodes_last_root<F, E>(&mut self, f: &mut F) -> Result<(), E>
     where F: FnMut(&mut Self) -> Result<(), E>
     {
-        for child in self.childs_mut().values_mut() {
-            child.try_map_nodes_last_root(f)?
+        // 先根序收集所有节点, 其逆序中每个节点都在其子孙节点之后
+        // 所有指针均由其父节点的指针派生, 且按后派生先使用的顺序使用
+        let mut nodes: Vec<*mut Self> = Vec::new();
+        let mut stack: Vec<*mut Self> = vec![self];
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            stack.extend(unsafe { &mut *node }
+                .childs_mut()
+                .values_mut()
+                .map(|child| child as *mut Self));
+        }
+        for node in nodes.into_iter().rev() {
+            f(unsafe { &mut *node })?;
         }
-        f(self)?;
         Ok(())
     }
 }
 
-impl<T> From<TrieTree<T>> for TrieNode<T>
-where T: Hash + Eq
-{
-    fn from(tree: TrieTree<T>) -> Self {
+impl<T, S> From<TrieTree<T, S>> for TrieNode<T, S> {
+    fn from(tree: TrieTree<T, S>) -> Self {
         tree.root
     }
 }
 
-impl<T> TrieNode<T>
-where T: Hash + Eq
+impl<T, S> TrieNode<T, S>
+where T: Hash + Eq,
+      S: BuildHasher,
 {
     /// 根据键获取子节点
     pub fn get_child(&self, query: impl Borrow<T>) -> Option<&Self> {
@@ -173,8 +271,13 @@ where T: Hash + Eq
     }
 
     /// 获取子节点, 如果没有该节点则将其插入
-    fn get_or_insert_child(&mut self, data: T) -> &mut Self {
-        self.childs.entry(data).or_default()
+    /// 新节点使用该节点的哈希构建器的克隆
+    fn get_or_insert_child(&mut self, data: T) -> &mut Self
+    where S: Clone
+    {
+        let hasher = self.childs.hasher().clone();
+        self.childs.entry(data)
+            .or_insert_with(|| Self::with_hasher(hasher))
     }
 
     /// 从子节点中删除一个节点
@@ -185,37 +288,46 @@ where T: Hash + Eq
     /// 删除一个分支
     /// 返回是否删除成功
     /// 当达到给定查询最后一个元素时返回是否删除成功
-    /// 然后这个结果会一直被传递到最外层
-    /// 在递回时会执行清理函数, 不管是否删除成功
+    /// 然后从下至上对路径上每个节点执行清理函数, 不管是否删除成功
     fn removed_do<Q>(
         &mut self,
-        mut iter: impl Iterator<Item = Q>,
+        iter: impl Iterator<Item = Q>,
         cleaner: &impl Fn(&mut Self, Q),
         ) -> bool
     where Q: Borrow<T>
     {
-        if let Some(query) = iter.next() {
+        // 记录路径上的节点指针以及查询值
+        // 每个节点指针均由上一个节点派生, 清理时按后派生先使用的顺序使用
+        let mut path: Vec<(*mut Self, Q)> = Vec::new();
+        let mut node: *mut Self = self;
+        let mut is_removed = true;
+        for query in iter {
             // 查询过程
-            if let Some(node) = self.get_child_mut(query.borrow()) {
+            if let Some(child) = unsafe { &mut *node }.get_child_mut(query.borrow()) {
                 // 继续查询
-                let is_removed = node.removed_do(iter, cleaner);
-                cleaner(self, query);
-                is_removed
+                path.push((node, query));
+                node = child;
             } else {
                 // 中途查询到空节点, 未能成功查询到
-                false
+                is_removed = false;
+                break;
             }
-        } else {
+        }
+        if is_removed {
             // 查询到尾部节点
-            let stop = self.stop();
-            if stop {
+            let node = unsafe { &mut *node };
+            is_removed = node.stop();
+            if is_removed {
                 // 是一个终止节点, 成功删除
-                self.unset_stop();
+                node.unset_stop();
             } else {
                 // 不是一个终止节点, 删除失败, 没有可以删的东西
             }
-            stop
         }
+        for (node, query) in path.into_iter().rev() {
+            cleaner(unsafe { &mut *node }, query);
+        }
+        is_removed
     }
 
     /// 从树中移除一个值串
@@ -278,8 +390,25 @@ where T: Hash + Eq
         Some(root)
     }
 
+    /// 查询值串中在树中的最长前缀的长度
+    /// 如果没有任何前缀在树中则返回`None`
+    pub fn longest_prefix<Q>(&self, iter: impl Iterator<Item = Q>) -> Option<usize>
+    where Q: Borrow<T>
+    {
+        let mut root = self;
+        let mut res = root.is_stop().then_some(0);
+        for (i, query) in iter.enumerate() {
+            let Some(child) = root.get_child(query) else { break };
+            root = child;
+            if root.is_stop() {
+                res = Some(i + 1);
+            }
+        }
+        res
+    }
+
     /// 查询值串并给出后序元素的迭代器
-    pub fn query_iter<Q>(&self, iter: impl Iterator<Item = Q>) -> Option<Iter<T>>
+    pub fn query_iter<Q>(&self, iter: impl Iterator<Item = Q>) -> Option<Iter<'_, T, S>>
     where Q: Borrow<T>
     {
         self.query_node(iter)
@@ -289,7 +418,9 @@ where T: Hash + Eq
     /// 插入一串值
     /// 返回是否插入成功
     /// 当值串已存在则插入失败
-    pub fn insert(&mut self, iter: impl Iterator<Item = T>) -> bool {
+    pub fn insert(&mut self, iter: impl Iterator<Item = T>) -> bool
+    where S: Clone
+    {
         let mut root = self;
         for data in iter {
             root = root.get_or_insert_child(data)